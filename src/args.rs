@@ -0,0 +1,37 @@
+use crate::game::resources::{GameMode, GameSeed};
+
+/// Command line flags, read once at launch.
+pub struct LaunchArgs {
+    pub terminal: bool,
+    pub mode: GameMode,
+    pub seed: GameSeed,
+}
+
+impl LaunchArgs {
+    pub fn parse() -> Self {
+        let mut launch_args = Self {
+            terminal: false,
+            mode: GameMode::default(),
+            seed: GameSeed::default(),
+        };
+
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                // `--terminal` swaps the window and sprites for glyphs on stdout
+                "--terminal" => launch_args.terminal = true,
+                // `--explorer` turns off the survival pressure
+                "--explorer" => launch_args.mode = GameMode::Explorer,
+                // `--seed <number>` fixes the unidentified item names, levels and AI still roll freely
+                "--seed" => {
+                    if let Some(seed) = args.next().and_then(|arg| arg.parse().ok()) {
+                        launch_args.seed = GameSeed(seed);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        launch_args
+    }
+}
@@ -5,3 +5,5 @@ pub const SCALE: f32 = 1.0;
 pub const BASE_GRID_SIZE: f32 = 16.0;
 pub const GRID_SIZE: f32 = SCALE * BASE_GRID_SIZE;
 pub const TILESET_PATH: &str = "sprites/tileset_2_16.png";
+pub const SETTINGS_PATH: &str = "settings.cfg";
+pub const HUD_HEIGHT: f32 = 40.0;
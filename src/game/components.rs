@@ -1,41 +1,382 @@
 use bevy::prelude::*;
+use bevy::utils::HashSet;
 
 use crate::game::directions::GridDirection;
-use crate::game::model::*;
+use crate::game::model::{AiKind, AiState, HungerState, StatusEffect, StatusEffectKind};
+use crate::game::templates::{
+    EquipSlot, ItemKind, MonsterKind, RangedStats, StatModifiers, TrapKind,
+};
 use crate::game::vector::GridVector;
 
 #[derive(Component, Debug)]
 pub struct MapEntity;
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FlipRule {
+    Never,
+    // mirror the sprite when facing west
+    FaceDirection,
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Renderable {
+    pub sprite_index: usize,
+    pub layer: f32,
+    pub tint: Color,
+    pub flip: FlipRule,
+}
+
+impl Renderable {
+    pub fn new(sprite_index: usize, layer: f32) -> Self {
+        Self {
+            sprite_index,
+            layer,
+            tint: Color::WHITE,
+            flip: FlipRule::Never,
+        }
+    }
+
+    pub fn with_flip(mut self, flip: FlipRule) -> Self {
+        self.flip = flip;
+        self
+    }
+}
+
 #[derive(Component, Debug, Default)]
 pub struct GridPosition {
     pub coordinates: GridVector,
     pub direction: Option<GridDirection>,
 }
 
+#[derive(Component, Debug)]
+pub struct Floor;
+
 #[derive(Component, Debug)]
 pub struct Solid;
 
 #[derive(Component, Debug)]
-pub struct Character {
-    pub character_type: CharacterType,
+pub struct Character;
+
+#[derive(Component, Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub enum Faction {
+    Player,
+    Vermin,
+    Greenskins,
+    Undead,
+    Wildlife,
+}
+
+#[derive(Component, Deref, DerefMut, Debug, Default)]
+pub struct Grudges(pub HashSet<Entity>);
+
+#[derive(Component, Debug)]
+pub struct Follower {
+    pub leader: Entity,
+}
+
+impl Follower {
+    pub fn new(leader: Entity) -> Self {
+        Self { leader }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Health {
+    pub current: i32,
+    pub max: i32,
+}
+
+impl Health {
+    pub fn new(max: i32) -> Self {
+        Self { current: max, max }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0
+    }
+
+    pub fn fraction(&self) -> f32 {
+        self.current as f32 / self.max as f32
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Vision {
+    pub radius: i32,
+}
+
+impl Vision {
+    pub fn new(radius: i32) -> Self {
+        Self { radius }
+    }
+}
+
+/// Lights tiles within `radius` that it has line of sight to, fading with distance.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct LightSource {
+    pub radius: i32,
+    pub color: Color,
+}
+
+impl LightSource {
+    pub const TORCH: Self = Self::new(5, Color::rgb(1.0, 0.7, 0.4));
+    pub const LANTERN: Self = Self::new(3, Color::rgb(1.0, 0.9, 0.7));
+
+    pub const fn new(radius: i32, color: Color) -> Self {
+        Self { radius, color }
+    }
+
+    pub fn intensity_at(&self, distance: i32) -> f32 {
+        (1.0 - distance as f32 / (self.radius + 1) as f32).max(0.0)
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Torch;
+
+#[derive(Component, Debug)]
+pub struct Ai {
+    pub kind: AiKind,
+    pub state: AiState,
+}
+
+impl Ai {
+    pub fn new(kind: AiKind) -> Self {
+        Self {
+            kind,
+            state: kind.initial_state(),
+        }
+    }
+}
+
+#[derive(Component, Deref, DerefMut, Debug, Default)]
+pub struct StatusEffects(pub Vec<StatusEffect>);
+
+impl StatusEffects {
+    pub fn add(&mut self, effect: StatusEffect) {
+        let Some(existing) = self
+            .iter_mut()
+            .find(|existing| existing.kind == effect.kind)
+        else {
+            self.push(effect);
+            return;
+        };
+
+        existing.remaining = existing.remaining.max(effect.remaining);
+        if effect.kind.stacks() {
+            existing.magnitude += effect.magnitude;
+        } else {
+            existing.magnitude = existing.magnitude.max(effect.magnitude);
+        }
+    }
+
+    pub fn has(&self, kind: StatusEffectKind) -> bool {
+        self.iter().any(|effect| effect.kind == kind)
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        let mut multiplier = 1.0;
+        if self.has(StatusEffectKind::Haste) {
+            multiplier *= 0.5;
+        }
+        if self.has(StatusEffectKind::Slow) {
+            multiplier *= 2.0;
+        }
+        multiplier
+    }
+}
+
+#[derive(Component, Deref, DerefMut, Debug)]
+pub struct RangedAttack(pub RangedStats);
+
+#[derive(Component, Deref, DerefMut, Debug, Default)]
+pub struct Inventory(pub Vec<Entity>);
+
+#[derive(Component, Debug, Default)]
+pub struct Equipment {
+    pub weapon: Option<Entity>,
+    pub armour: Option<Entity>,
+    pub ring: Option<Entity>,
+}
+
+impl Equipment {
+    // returns the item that was in the slot before
+    pub fn set(&mut self, slot: EquipSlot, item: Option<Entity>) -> Option<Entity> {
+        let current = match slot {
+            EquipSlot::Weapon => &mut self.weapon,
+            EquipSlot::Armour => &mut self.armour,
+            EquipSlot::Ring => &mut self.ring,
+        };
+        std::mem::replace(current, item)
+    }
+
+    pub fn contains(&self, item: Entity) -> bool {
+        self.items().any(|other| other == item)
+    }
+
+    pub fn remove(&mut self, item: Entity) {
+        for slot in [&mut self.weapon, &mut self.armour, &mut self.ring] {
+            if *slot == Some(item) {
+                *slot = None;
+            }
+        }
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = Entity> {
+        [self.weapon, self.armour, self.ring].into_iter().flatten()
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Satiation {
+    pub current: f32,
+    pub max: f32,
+    starvation: f32,
+}
+
+impl Satiation {
+    pub fn new(max: f32) -> Self {
+        Self {
+            current: max,
+            max,
+            starvation: 0.0,
+        }
+    }
+
+    // returns the number of whole time units spent starving
+    pub fn consume(&mut self, amount: f32) -> i32 {
+        let remaining = self.current - amount;
+        self.current = remaining.max(0.0);
+        if remaining >= 0.0 {
+            self.starvation = 0.0;
+            return 0;
+        }
+
+        self.starvation -= remaining;
+        let ticks = self.starvation.floor();
+        self.starvation -= ticks;
+        ticks as i32
+    }
+
+    pub fn restore(&mut self, amount: f32) {
+        self.current = (self.current + amount).min(self.max);
+    }
+
+    pub fn state(&self) -> HungerState {
+        HungerState::from_fraction(self.current / self.max)
+    }
+
+    pub fn is_hungry(&self) -> bool {
+        self.state() != HungerState::Satiated
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Experience {
+    pub level: u32,
+    pub points: u32,
+    pub unspent_perks: u32,
+}
+
+impl Experience {
+    pub fn next_level_points(&self) -> u32 {
+        10 * self.level * (self.level + 1) / 2
+    }
+}
+
+impl Default for Experience {
+    fn default() -> Self {
+        Self {
+            level: 1,
+            points: 0,
+            unspent_perks: 0,
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct CombatStats {
+    pub attack: i32,
+    pub defense: i32,
+}
+
+impl CombatStats {
+    pub fn new(attack: i32, defense: i32) -> Self {
+        Self { attack, defense }
+    }
+
+    pub fn attack_with(&self, modifiers: &StatModifiers, satiation: Option<&Satiation>) -> i32 {
+        let hunger_penalty = satiation.map_or(0, |satiation| satiation.state().attack_penalty());
+        self.attack + modifiers.attack - hunger_penalty
+    }
+
+    pub fn defense_with(&self, modifiers: &StatModifiers) -> i32 {
+        self.defense + modifiers.defense
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Monster {
+    pub kind: MonsterKind,
+}
+
+impl Monster {
+    pub fn new(kind: MonsterKind) -> Self {
+        Self { kind }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Item {
+    pub kind: ItemKind,
+}
+
+impl Item {
+    pub fn new(kind: ItemKind) -> Self {
+        Self { kind }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Trap {
+    pub kind: TrapKind,
+    pub hidden: bool,
 }
 
-impl Character {
-    pub fn new(character_type: CharacterType) -> Self {
-        Self { character_type }
+impl Trap {
+    pub fn new(kind: TrapKind) -> Self {
+        Self { kind, hidden: true }
     }
 }
 
+#[derive(Component, Debug)]
+pub struct Stairs;
+
 #[derive(Component, Debug)]
 pub struct Door {
     // TODO use enum OPEN, CLOSE, LOCKED
     pub closed: bool,
+    // looks and behaves like a wall until found
+    pub secret: bool,
 }
 
 impl Door {
     pub fn new(closed: bool) -> Self {
-        Self { closed }
+        Self {
+            closed,
+            secret: false,
+        }
+    }
+
+    pub fn secret() -> Self {
+        Self {
+            closed: true,
+            secret: true,
+        }
+    }
+
+    pub fn reveal(&mut self) {
+        self.secret = false;
     }
 
     pub fn open(&mut self) {
@@ -52,3 +393,52 @@ pub struct Cooldown(pub f32);
 
 #[derive(Component, Debug)]
 pub struct PlayerControlled;
+
+#[derive(Component, Debug)]
+pub struct Sneaking;
+
+#[derive(Component, Debug)]
+pub struct Travelling {
+    pub destination: GridVector,
+}
+
+impl Travelling {
+    pub fn new(destination: GridVector) -> Self {
+        Self { destination }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Resting {
+    pub turns_left: u32,
+}
+
+impl Resting {
+    pub fn new(turns_left: u32) -> Self {
+        Self { turns_left }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct NaturalRegeneration {
+    // time units per point of health
+    pub interval: f32,
+    progress: f32,
+}
+
+impl NaturalRegeneration {
+    pub fn new(interval: f32) -> Self {
+        Self {
+            interval,
+            progress: 0.0,
+        }
+    }
+
+    // returns the number of health points regenerated
+    pub fn advance(&mut self, elapsed: f32) -> i32 {
+        self.progress += elapsed;
+        let points = (self.progress / self.interval).floor();
+        self.progress -= points * self.interval;
+        points as i32
+    }
+}
@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+use crate::game::vector::GridVector;
+
+#[derive(Event, Debug, Clone, Copy)]
+pub enum CombatEvent {
+    Attack {
+        attacker: Entity,
+        target: GridVector,
+    },
+    // coordinates are missing when the target is not on the map
+    Damage {
+        target: Entity,
+        coordinates: Option<GridVector>,
+        amount: i32,
+    },
+    Heal {
+        target: Entity,
+        coordinates: Option<GridVector>,
+        amount: i32,
+    },
+    // a projectile that landed without hitting anyone
+    Miss {
+        coordinates: GridVector,
+    },
+}
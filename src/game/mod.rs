@@ -1,13 +1,15 @@
 use bevy::prelude::*;
 
-use self::{resources::*, systems::*};
+use self::{events::*, resources::*, systems::*};
 
 pub mod components;
 pub mod directions;
+pub mod events;
 mod model;
-mod resources;
+pub mod resources;
 pub mod services;
 mod systems;
+pub mod templates;
 pub mod vector;
 
 pub struct GamePlugin;
@@ -15,8 +17,22 @@ pub struct GamePlugin;
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<SelectedEntity>()
-            .init_resource::<InputCooldown>()
+            .init_resource::<AnimationLock>()
             .init_resource::<AcceptInput>()
+            .init_resource::<GamePaused>()
+            .init_resource::<GameTime>()
+            .init_resource::<DungeonDepth>()
+            .init_resource::<GameSeed>()
+            .init_resource::<GameMode>()
+            .init_resource::<HungerSettings>()
+            .init_resource::<ItemAppearances>()
+            .init_resource::<IdentifiedItems>()
+            .init_resource::<VisibleTiles>()
+            .init_resource::<ExploredTiles>()
+            .init_resource::<LightMap>()
+            .init_resource::<SpatialIndex>()
+            .init_resource::<FactionTable>()
+            .add_event::<CombatEvent>()
             .add_plugins(GameSystemsPlugin);
     }
 }
@@ -1,7 +1,167 @@
-#[derive(Debug, Eq, PartialEq)]
-pub enum CharacterType {
-    Player,
-    Monster,
+use crate::game::vector::GridVector;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Attitude {
+    Friendly,
+    Neutral,
+    Hostile,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum AiKind {
+    Aggressive,
+    Cautious,
+    Sleeper,
+}
+
+impl AiKind {
+    pub fn initial_state(&self) -> AiState {
+        match self {
+            Self::Aggressive | Self::Cautious => AiState::Wandering,
+            Self::Sleeper => AiState::Sleeping,
+        }
+    }
+
+    pub fn flee_threshold(&self) -> Option<f32> {
+        match self {
+            Self::Aggressive => None,
+            Self::Cautious => Some(0.5),
+            Self::Sleeper => Some(0.25),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum AiState {
+    Sleeping,
+    Wandering,
+    Hunting { target: GridVector },
+    Investigating { target: GridVector },
+    Fleeing { from: GridVector },
+    Searching { around: GridVector, turns_left: u32 },
+}
+
+impl AiState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Sleeping => "sleeping",
+            Self::Wandering => "wandering",
+            Self::Hunting { .. } => "hunting",
+            Self::Investigating { .. } => "investigating",
+            Self::Fleeing { .. } => "fleeing",
+            Self::Searching { .. } => "searching",
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum HungerState {
+    Satiated,
+    Hungry,
+    Weak,
+    Starving,
+}
+
+impl HungerState {
+    pub fn from_fraction(fraction: f32) -> Self {
+        if fraction <= 0.0 {
+            Self::Starving
+        } else if fraction < 0.1 {
+            Self::Weak
+        } else if fraction < 0.25 {
+            Self::Hungry
+        } else {
+            Self::Satiated
+        }
+    }
+
+    pub fn attack_penalty(&self) -> i32 {
+        match self {
+            Self::Satiated | Self::Hungry => 0,
+            Self::Weak | Self::Starving => 1,
+        }
+    }
+
+    pub fn warning(&self) -> &'static str {
+        match self {
+            Self::Satiated => "you are no longer hungry",
+            Self::Hungry => "you are getting hungry",
+            Self::Weak => "you feel weak from hunger",
+            Self::Starving => "you are starving!",
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Perk {
+    Toughness,
+    Might,
+    Guard,
+    KeenEyes,
+}
+
+impl Perk {
+    pub const ALL: [Self; 4] = [Self::Toughness, Self::Might, Self::Guard, Self::KeenEyes];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Toughness => "toughness (+5 max health)",
+            Self::Might => "might (+1 attack)",
+            Self::Guard => "guard (+1 defense)",
+            Self::KeenEyes => "keen eyes (+1 sight)",
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum StatusEffectKind {
+    Poison,
+    Regeneration,
+    Haste,
+    Slow,
+    Confusion,
+    Blindness,
+    Fear,
+}
+
+impl StatusEffectKind {
+    pub fn stacks(&self) -> bool {
+        matches!(self, Self::Poison | Self::Regeneration)
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    pub remaining: f32,
+    pub magnitude: i32,
+    progress: f32,
+}
+
+impl StatusEffect {
+    pub const fn new(kind: StatusEffectKind, duration: f32, magnitude: i32) -> Self {
+        Self {
+            kind,
+            remaining: duration,
+            magnitude,
+            progress: 0.0,
+        }
+    }
+
+    // returns the number of whole time units that passed, for per-turn effects
+    pub fn advance(&mut self, elapsed: f32) -> i32 {
+        let elapsed = elapsed.min(self.remaining);
+        self.remaining -= elapsed;
+        self.progress += elapsed;
+
+        let ticks = self.progress.floor();
+        self.progress -= ticks;
+        ticks as i32
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining <= 0.0
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
@@ -1,4 +1,14 @@
 use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+use crate::constants::SETTINGS_PATH;
+use crate::game::components::Faction;
+use crate::game::model::{Attitude, Perk};
+use crate::game::templates::{ItemClass, ItemKind, POTION_APPEARANCES, SCROLL_APPEARANCES};
+use crate::game::vector::GridVector;
+use crate::settings_file;
 
 #[derive(Resource, Deref, DerefMut, Default)]
 pub struct SelectedEntity(pub Option<Entity>);
@@ -6,17 +16,244 @@ pub struct SelectedEntity(pub Option<Entity>);
 #[derive(Resource, Default, PartialEq)]
 pub struct AcceptInput(pub bool);
 
+// set by the render backend while it is still showing the last turn
+#[derive(Resource, Default)]
+pub struct AnimationLock(pub bool);
+
+// set while a menu has the keyboard
+#[derive(Resource, Default, PartialEq)]
+pub struct GamePaused(pub bool);
+
+#[derive(Resource, Deref, DerefMut, Default)]
+pub struct GameTime(pub f32);
+
 #[derive(Resource, Deref, DerefMut)]
-pub struct InputCooldown(pub Timer);
+pub struct DungeonDepth(pub u32);
+
+impl Default for DungeonDepth {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+#[derive(Resource, Deref, Clone, Copy)]
+pub struct GameSeed(pub u64);
+
+impl Default for GameSeed {
+    fn default() -> Self {
+        Self(thread_rng().gen())
+    }
+}
+
+#[derive(Resource, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum GameMode {
+    #[default]
+    Standard,
+    Explorer,
+}
+
+/// Read from the `hunger_*` keys of [`SETTINGS_PATH`], explorer mode always turns it off.
+#[derive(Resource)]
+pub struct HungerSettings {
+    pub enabled: bool,
+    pub max_satiation: f32,
+    // satiation lost per unit of game time
+    pub rate: f32,
+}
+
+impl HungerSettings {
+    pub fn load(path: &str) -> Self {
+        let mut settings = Self {
+            enabled: true,
+            max_satiation: 1000.0,
+            rate: 1.0,
+        };
+        for (key, value) in settings_file::read_entries(path) {
+            let applied = match key.as_str() {
+                "hunger_enabled" => value.parse().ok().map(|enabled| settings.enabled = enabled),
+                "hunger_max_satiation" => value
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|max_satiation| *max_satiation > 0.0)
+                    .map(|max_satiation| settings.max_satiation = max_satiation),
+                "hunger_rate" => value
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|rate| *rate >= 0.0)
+                    .map(|rate| settings.rate = rate),
+                _ => continue,
+            };
+            if applied.is_none() {
+                warn!("ignoring invalid setting {} = {}", key, value);
+            }
+        }
+
+        settings
+    }
+}
+
+impl FromWorld for HungerSettings {
+    fn from_world(world: &mut World) -> Self {
+        let mut settings = Self::load(SETTINGS_PATH);
+        if *world.resource::<GameMode>() == GameMode::Explorer {
+            settings.enabled = false;
+        }
+        settings
+    }
+}
+
+#[derive(Resource, Deref, DerefMut, Default)]
+pub struct VisibleTiles(pub HashSet<GridVector>);
+
+/// Light falling on each tile, as colour channels summed over all sources.
+#[derive(Resource, Deref, DerefMut, Default)]
+pub struct LightMap(pub HashMap<GridVector, Color>);
+
+#[derive(Resource, Deref, DerefMut, Default)]
+pub struct ExploredTiles(pub HashSet<GridVector>);
+
+#[derive(Resource, Deref)]
+pub struct ItemAppearances(pub HashMap<ItemKind, &'static str>);
+
+impl FromWorld for ItemAppearances {
+    fn from_world(world: &mut World) -> Self {
+        let mut rng = StdRng::seed_from_u64(world.resource::<GameSeed>().0);
+        let mut potions = POTION_APPEARANCES.to_vec();
+        let mut scrolls = SCROLL_APPEARANCES.to_vec();
+        potions.shuffle(&mut rng);
+        scrolls.shuffle(&mut rng);
 
-impl InputCooldown {
-    pub fn new() -> Self {
-        Self(Timer::from_seconds(0.15, TimerMode::Once))
+        let mut appearances = HashMap::new();
+        for kind in ItemKind::ALL {
+            let appearance = match kind.template().class {
+                ItemClass::Potion => potions.pop(),
+                ItemClass::Scroll => scrolls.pop(),
+                _ => None,
+            };
+            if let Some(appearance) = appearance {
+                appearances.insert(kind, appearance);
+            }
+        }
+
+        Self(appearances)
+    }
+}
+
+impl ItemAppearances {
+    pub fn name_of(&self, kind: ItemKind, identified: &IdentifiedItems) -> &'static str {
+        if identified.contains(&kind) {
+            return kind.template().name;
+        }
+
+        self.get(&kind).copied().unwrap_or(kind.template().name)
+    }
+}
+
+#[derive(Resource, Deref, DerefMut, Default)]
+pub struct IdentifiedItems(pub HashSet<ItemKind>);
+
+#[derive(Resource, Default)]
+pub struct SpatialIndex {
+    entities: HashMap<GridVector, Vec<Entity>>,
+    positions: HashMap<Entity, GridVector>,
+}
+
+impl SpatialIndex {
+    pub fn insert(&mut self, entity: Entity, coordinates: GridVector) {
+        if self.positions.get(&entity) == Some(&coordinates) {
+            return;
+        }
+
+        self.remove(entity);
+        self.positions.insert(entity, coordinates);
+        self.entities.entry(coordinates).or_default().push(entity);
+    }
+
+    pub fn remove(&mut self, entity: Entity) {
+        let Some(coordinates) = self.positions.remove(&entity) else {
+            return;
+        };
+
+        if let Some(entities) = self.entities.get_mut(&coordinates) {
+            entities.retain(|other| *other != entity);
+            if entities.is_empty() {
+                self.entities.remove(&coordinates);
+            }
+        }
+    }
+
+    pub fn entities_at(&self, coordinates: &GridVector) -> &[Entity] {
+        self.entities
+            .get(coordinates)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+}
+
+#[derive(Resource)]
+pub struct FactionTable {
+    attitudes: HashMap<(Faction, Faction), Attitude>,
+}
+
+impl FactionTable {
+    pub fn get_attitude(&self, faction: Faction, other: Faction) -> Attitude {
+        if faction == other {
+            return Attitude::Friendly;
+        }
+
+        self.attitudes
+            .get(&(faction, other))
+            .copied()
+            .unwrap_or(Attitude::Neutral)
+    }
+
+    pub fn set_attitude(&mut self, faction: Faction, other: Faction, attitude: Attitude) {
+        self.attitudes.insert((faction, other), attitude);
+        self.attitudes.insert((other, faction), attitude);
     }
 }
 
-impl Default for InputCooldown {
+impl Default for FactionTable {
     fn default() -> Self {
-        Self::new()
+        let mut table = Self {
+            attitudes: HashMap::new(),
+        };
+
+        table.set_attitude(Faction::Player, Faction::Vermin, Attitude::Hostile);
+        table.set_attitude(Faction::Player, Faction::Greenskins, Attitude::Hostile);
+        table.set_attitude(Faction::Player, Faction::Undead, Attitude::Hostile);
+        table.set_attitude(Faction::Vermin, Faction::Wildlife, Attitude::Hostile);
+        table.set_attitude(Faction::Undead, Faction::Vermin, Attitude::Hostile);
+        table.set_attitude(Faction::Undead, Faction::Greenskins, Attitude::Hostile);
+        table.set_attitude(Faction::Undead, Faction::Wildlife, Attitude::Hostile);
+
+        table
     }
 }
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TargetingPurpose {
+    Look,
+    Fire,
+    Throw(Entity),
+    Travel,
+}
+
+#[derive(Resource)]
+pub struct Targeting {
+    pub purpose: TargetingPurpose,
+    pub origin: GridVector,
+    // only used by projectile purposes
+    pub range: i32,
+    pub cursor: GridVector,
+    pub candidates: Vec<Entity>,
+    pub candidate_index: usize,
+    pub path: Vec<GridVector>,
+    pub description: String,
+}
+
+#[derive(Resource)]
+pub struct PerkChoice {
+    pub entity: Entity,
+    pub options: Vec<Perk>,
+}
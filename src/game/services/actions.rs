@@ -1,18 +1,58 @@
 use bevy::prelude::*;
+use rand::prelude::*;
 
 use crate::game::components::*;
 use crate::game::directions::GridDirection;
-use crate::game::model::CharacterType;
-use crate::game::services::utils;
+use crate::game::events::CombatEvent;
+use crate::game::model::Attitude;
+use crate::game::resources::{IdentifiedItems, SpatialIndex};
+use crate::game::services::{item_effects, levels, noise, progression, traps, utils};
 use crate::game::vector::GridVector;
 
+const DOOR_NOISE_RADIUS: i32 = 4;
+const ATTACK_NOISE_RADIUS: i32 = 5;
+const SHOOT_NOISE_RADIUS: i32 = 2;
+pub const THROW_RANGE: i32 = 6;
+const SEARCH_CHANCE: f64 = 0.5;
+const PASSIVE_SEARCH_CHANCE: f64 = 0.1;
+
 pub enum MoveType {
     Walk,
     Run,
+    Sneak,
 }
 
 pub fn wait(entity: Entity, world: &mut World) -> bool {
+    // standing still for a while turns up things too, just less reliably
+    if world.get::<PlayerControlled>(entity).is_some() {
+        traps::search_for_traps(entity, PASSIVE_SEARCH_CHANCE, world);
+        traps::search_for_secret_doors(entity, PASSIVE_SEARCH_CHANCE, world);
+    }
+    update_cooldown(entity, 1.0, world);
+    true
+}
+
+pub fn search(entity: Entity, world: &mut World) -> bool {
+    traps::search_for_traps(entity, SEARCH_CHANCE, world);
+    traps::search_for_secret_doors(entity, SEARCH_CHANCE, world);
+    update_cooldown(entity, 1.0, world);
+    true
+}
+
+pub fn attempt_to_descend(entity: Entity, world: &mut World) -> bool {
+    let coordinates = world.get::<GridPosition>(entity).unwrap().coordinates;
+    let on_stairs = world
+        .resource::<SpatialIndex>()
+        .entities_at(&coordinates)
+        .iter()
+        .any(|other| world.get::<Stairs>(*other).is_some());
+    if !on_stairs {
+        return false;
+    }
+
+    levels::descend(world);
     update_cooldown(entity, 1.0, world);
+
     true
 }
 
@@ -27,54 +67,324 @@ pub fn attempt_to_move(
         return false;
     }
 
+    utils::set_coordinates(entity, coordinates, world);
     let mut position = world.get_mut::<GridPosition>(entity).unwrap();
-    position.coordinates = coordinates;
     if position.direction.is_some() {
         position.direction = Some(direction);
     }
 
-    let cooldown = match move_type {
-        MoveType::Walk => 1.0,
-        MoveType::Run => 0.5,
+    let (cooldown, noise_radius) = match move_type {
+        MoveType::Walk => (1.0, 1),
+        MoveType::Run => (0.5, 6),
+        MoveType::Sneak => (2.0, 0),
     };
     update_cooldown(entity, cooldown, world);
+    noise::emit_noise(coordinates, noise_radius, Some(entity), world);
+    traps::trigger_trap_at(entity, coordinates, world);
 
     true
 }
 
-pub fn attempt_to_attack(
+pub fn attempt_to_attack(entity: Entity, coordinates: GridVector, world: &mut World) -> bool {
+    let Some(target_entity) = utils::get_character_at(coordinates, world) else {
+        return false;
+    };
+
+    if utils::get_attitude(entity, target_entity, world) == Attitude::Friendly {
+        return false;
+    }
+
+    world.send_event(CombatEvent::Attack {
+        attacker: entity,
+        target: coordinates,
+    });
+    let attack = utils::get_attack(entity, world);
+    hit(entity, target_entity, attack, world);
+
+    let on_hit = world
+        .get::<Monster>(entity)
+        .and_then(|monster| monster.kind.template().on_hit);
+    if let (Some(effect), Some(mut status_effects)) =
+        (on_hit, world.get_mut::<StatusEffects>(target_entity))
+    {
+        status_effects.add(effect);
+    }
+
+    update_cooldown(entity, 1.0, world);
+    noise::emit_noise(coordinates, ATTACK_NOISE_RADIUS, Some(entity), world);
+
+    true
+}
+
+pub fn attempt_to_shoot(entity: Entity, target: GridVector, world: &mut World) -> bool {
+    let Some(ranged_stats) = utils::get_ranged_stats(entity, world) else {
+        return false;
+    };
+
+    let origin = world.get::<GridPosition>(entity).unwrap().coordinates;
+    if origin == target {
+        return false;
+    }
+
+    let (path, hit_entity) = utils::trace_projectile(origin, target, ranged_stats.range, world);
+    if let Some(hit_entity) = hit_entity {
+        hit(entity, hit_entity, ranged_stats.damage, world);
+    } else {
+        send_miss(&path, world);
+    }
+    update_cooldown(entity, 1.0, world);
+    noise::emit_noise(origin, SHOOT_NOISE_RADIUS, Some(entity), world);
+
+    true
+}
+
+pub fn attempt_to_throw(
     entity: Entity,
-    coordinates: GridVector,
-    character_type: CharacterType,
+    item: Entity,
+    target: GridVector,
     world: &mut World,
 ) -> bool {
-    let Some(target_entity) = utils::get_character_at(character_type, coordinates, world) else {
+    let origin = world.get::<GridPosition>(entity).unwrap().coordinates;
+    if origin == target {
+        return false;
+    }
+
+    if !utils::take_from_inventory(entity, item, world) {
+        return false;
+    }
+
+    let (path, hit_entity) = utils::trace_projectile(origin, target, THROW_RANGE, world);
+    if let Some(hit_entity) = hit_entity {
+        let throw_damage = world
+            .get::<Item>(item)
+            .unwrap()
+            .kind
+            .template()
+            .throw_damage;
+        hit(entity, hit_entity, throw_damage, world);
+    } else {
+        send_miss(&path, world);
+    }
+
+    let landing = path.last().copied().unwrap_or(origin);
+    utils::place_on_map(item, landing, world);
+    update_cooldown(entity, 1.0, world);
+
+    true
+}
+
+pub fn attempt_to_pick_up(entity: Entity, world: &mut World) -> bool {
+    let coordinates = world.get::<GridPosition>(entity).unwrap().coordinates;
+
+    let Some(item) = utils::get_item_at(coordinates, world) else {
         return false;
     };
 
-    // TODO
-    world.despawn(target_entity);
+    let Some(mut inventory) = world.get_mut::<Inventory>(entity) else {
+        return false;
+    };
+    inventory.push(item);
+    let slot = inventory.len();
+
+    if world.get::<PlayerControlled>(entity).is_some() {
+        let kind = world.get::<Item>(item).unwrap().kind;
+        info!(
+            "you pick up the {} ({})",
+            utils::get_item_name(kind, world),
+            slot
+        );
+    }
+    utils::remove_from_map(item, world);
     update_cooldown(entity, 1.0, world);
 
     true
 }
 
+pub fn attempt_to_use(entity: Entity, item: Entity, world: &mut World) -> bool {
+    let kind = world.get::<Item>(item).unwrap().kind;
+    let Some(effect) = kind.template().use_effect else {
+        return false;
+    };
+
+    if !utils::take_from_inventory(entity, item, world) {
+        return false;
+    }
+
+    if world.resource_mut::<IdentifiedItems>().insert(kind) {
+        info!("it was a {}", kind.template().name);
+    }
+    utils::despawn(item, world);
+    item_effects::apply_use_effect(entity, effect, world);
+    update_cooldown(entity, 1.0, world);
+
+    true
+}
+
+pub fn attempt_to_toggle_equipment(entity: Entity, item: Entity, world: &mut World) -> bool {
+    let Some(slot) = world
+        .get::<Item>(item)
+        .unwrap()
+        .kind
+        .template()
+        .class
+        .slot()
+    else {
+        return false;
+    };
+
+    let Some(mut equipment) = world.get_mut::<Equipment>(entity) else {
+        return false;
+    };
+    if equipment.contains(item) {
+        equipment.remove(item);
+    } else {
+        equipment.set(slot, Some(item));
+    }
+    update_cooldown(entity, 1.0, world);
+
+    true
+}
+
+pub fn attempt_to_tame(entity: Entity, coordinates: GridVector, world: &mut World) -> bool {
+    let Some(target_entity) = utils::get_character_at(coordinates, world) else {
+        return false;
+    };
+
+    if utils::get_attitude(entity, target_entity, world) != Attitude::Neutral {
+        return false;
+    }
+
+    let Some(monster) = world.get::<Monster>(target_entity) else {
+        return false;
+    };
+
+    let tame_chance = monster.kind.template().tame_chance;
+    if tame_chance <= 0.0 {
+        return false;
+    }
+
+    if thread_rng().gen::<f32>() < tame_chance {
+        let faction = *world.get::<Faction>(entity).unwrap();
+        world
+            .entity_mut(target_entity)
+            .insert((faction, Follower::new(entity)));
+    }
+    update_cooldown(entity, 1.0, world);
+
+    true
+}
+
+pub fn attempt_to_disarm(entity: Entity, coordinates: GridVector, world: &mut World) -> bool {
+    let Some(trap_entity) = traps::get_trap_at(coordinates, world) else {
+        return false;
+    };
+
+    let trap = world.get::<Trap>(trap_entity).unwrap();
+    if trap.hidden {
+        return false;
+    }
+
+    let template = trap.kind.template();
+
+    // the cooldown goes first, a sprung trap may kill the disarmer
+    update_cooldown(entity, 1.0, world);
+    if thread_rng().gen::<f32>() < template.disarm_chance {
+        info!("you disarm the {}", template.name);
+        utils::despawn(trap_entity, world);
+    } else {
+        info!("you fail to disarm the {}", template.name);
+        traps::trigger_trap_at(entity, coordinates, world);
+    }
+
+    true
+}
+
 pub fn attempt_to_open_door(entity: Entity, coordinates: GridVector, world: &mut World) -> bool {
     let Some(door_entity) = utils::get_door_at(coordinates, world) else {
         return false;
     };
 
     let mut door = world.get_mut::<Door>(door_entity).unwrap();
-    if door.closed {
+    if door.closed && !door.secret {
         door.open();
         world.entity_mut(door_entity).remove::<Solid>();
         update_cooldown(entity, 1.0, world);
+        noise::emit_noise(coordinates, DOOR_NOISE_RADIUS, Some(entity), world);
         true
     } else {
         false
     }
 }
 
+fn hit(entity: Entity, target_entity: Entity, attack: i32, world: &mut World) {
+    if let Some(mut grudges) = world.get_mut::<Grudges>(target_entity) {
+        grudges.insert(entity);
+    }
+
+    let experience = world
+        .get::<Monster>(target_entity)
+        .map_or(0, |monster| monster.kind.template().experience);
+    let defense = utils::get_defense(target_entity, world);
+    apply_damage(target_entity, (attack - defense).max(1), world);
+
+    if world.get_entity(target_entity).is_none() {
+        progression::gain_experience(entity, experience, world);
+    }
+}
+
+fn send_miss(path: &[GridVector], world: &mut World) {
+    if let Some(coordinates) = path.last().copied() {
+        world.send_event(CombatEvent::Miss { coordinates });
+    }
+}
+
+pub fn heal(entity: Entity, amount: i32, world: &mut World) {
+    let coordinates = world
+        .get::<GridPosition>(entity)
+        .map(|position| position.coordinates);
+    let Some(mut health) = world.get_mut::<Health>(entity) else {
+        return;
+    };
+
+    let previous = health.current;
+    health.current = (health.current + amount).min(health.max);
+    let healed = health.current - previous;
+    if healed > 0 {
+        world.send_event(CombatEvent::Heal {
+            target: entity,
+            coordinates,
+            amount: healed,
+        });
+    }
+}
+
+pub fn apply_damage(entity: Entity, damage: i32, world: &mut World) {
+    let coordinates = world
+        .get::<GridPosition>(entity)
+        .map(|position| position.coordinates);
+    let Some(mut health) = world.get_mut::<Health>(entity) else {
+        return;
+    };
+
+    health.current -= damage;
+    let dead = health.is_dead();
+    world.entity_mut(entity).remove::<Resting>();
+    world.send_event(CombatEvent::Damage {
+        target: entity,
+        coordinates,
+        amount: damage,
+    });
+
+    if dead {
+        utils::drop_inventory(entity, world);
+        utils::despawn(entity, world);
+    }
+}
+
 fn update_cooldown(entity: Entity, cooldown: f32, world: &mut World) {
-    world.get_mut::<Cooldown>(entity).unwrap().0 += cooldown;
+    let speed_multiplier = world
+        .get::<StatusEffects>(entity)
+        .map_or(1.0, |status_effects| status_effects.speed_multiplier())
+        * utils::get_stat_modifiers(entity, world).speed_multiplier();
+    world.get_mut::<Cooldown>(entity).unwrap().0 += cooldown * speed_multiplier;
 }
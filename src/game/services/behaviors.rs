@@ -1,32 +1,52 @@
 use bevy::prelude::*;
 use rand::prelude::*;
 
-use crate::game::components::GridPosition;
+use crate::game::components::*;
 use crate::game::directions::GridDirection;
-use crate::game::model::CharacterType;
-use crate::game::services::{actions, player_actions, utils};
+use crate::game::model::{AiState, StatusEffectKind};
+use crate::game::resources::TargetingPurpose;
+use crate::game::services::{actions, player_actions, progression, targeting, utils};
 use crate::game::vector::GridVector;
 
+const SEARCH_TURNS: u32 = 10;
+const REST_TURNS: u32 = 100;
+
 pub fn player_behavior(entity: Entity, world: &mut World) -> bool {
+    if world.get::<Travelling>(entity).is_some() {
+        return travel(entity, world);
+    }
+
+    if world.get::<Resting>(entity).is_some() {
+        return rest(entity, world);
+    }
+
     let Some(player_action) = player_actions::get_player_action(world) else {
         return false;
     };
 
     match player_action {
         player_actions::PlayerAction::Wait => actions::wait(entity, world),
+        player_actions::PlayerAction::Rest => {
+            world.entity_mut(entity).insert(Resting::new(REST_TURNS));
+            rest(entity, world)
+        }
+        player_actions::PlayerAction::Search => actions::search(entity, world),
+        player_actions::PlayerAction::Descend => actions::attempt_to_descend(entity, world),
         player_actions::PlayerAction::MoveAttack(direction) => {
+            let direction = utils::confuse_direction(entity, direction, world);
             let coordinates = utils::get_shifted_coordinates(entity, direction, world).unwrap();
-            actions::attempt_to_attack(entity, coordinates, CharacterType::Monster, world)
+            let move_type = if world.get::<Sneaking>(entity).is_some() {
+                actions::MoveType::Sneak
+            } else {
+                actions::MoveType::Walk
+            };
+
+            actions::attempt_to_attack(entity, coordinates, world)
                 || actions::attempt_to_open_door(entity, coordinates, world)
-                || actions::attempt_to_move(
-                    entity,
-                    coordinates,
-                    direction,
-                    actions::MoveType::Walk,
-                    world,
-                )
+                || actions::attempt_to_move(entity, coordinates, direction, move_type, world)
         }
         player_actions::PlayerAction::Run(direction) => {
+            let direction = utils::confuse_direction(entity, direction, world);
             let coordinates = utils::get_shifted_coordinates(entity, direction, world).unwrap();
             actions::attempt_to_move(
                 entity,
@@ -36,13 +56,308 @@ pub fn player_behavior(entity: Entity, world: &mut World) -> bool {
                 world,
             )
         }
+        player_actions::PlayerAction::ToggleSneak => {
+            if world.get::<Sneaking>(entity).is_some() {
+                world.entity_mut(entity).remove::<Sneaking>();
+            } else {
+                world.entity_mut(entity).insert(Sneaking);
+            }
+            false
+        }
+        player_actions::PlayerAction::Tame(direction) => {
+            let coordinates = utils::get_shifted_coordinates(entity, direction, world).unwrap();
+            actions::attempt_to_tame(entity, coordinates, world)
+        }
+        player_actions::PlayerAction::Disarm(direction) => {
+            let coordinates = utils::get_shifted_coordinates(entity, direction, world).unwrap();
+            actions::attempt_to_disarm(entity, coordinates, world)
+        }
+        player_actions::PlayerAction::PickUp => actions::attempt_to_pick_up(entity, world),
+        player_actions::PlayerAction::StartTargeting(purpose) => {
+            targeting::start_targeting(entity, purpose, world)
+        }
+        player_actions::PlayerAction::AimThrow(slot) => {
+            let Some(item) = world
+                .get::<Inventory>(entity)
+                .and_then(|inventory| inventory.get(slot).copied())
+            else {
+                return false;
+            };
+            targeting::start_targeting(entity, TargetingPurpose::Throw(item), world)
+        }
+        player_actions::PlayerAction::Use(slot) => {
+            let Some(item) = world
+                .get::<Inventory>(entity)
+                .and_then(|inventory| inventory.get(slot).copied())
+            else {
+                return false;
+            };
+            actions::attempt_to_use(entity, item, world)
+        }
+        player_actions::PlayerAction::ToggleEquipment(slot) => {
+            let Some(item) = world
+                .get::<Inventory>(entity)
+                .and_then(|inventory| inventory.get(slot).copied())
+            else {
+                return false;
+            };
+            actions::attempt_to_toggle_equipment(entity, item, world)
+        }
+        player_actions::PlayerAction::ChoosePerk(index) => {
+            progression::choose_perk(index, world);
+            false
+        }
+        player_actions::PlayerAction::Fire(target) => {
+            actions::attempt_to_shoot(entity, target, world)
+        }
+        player_actions::PlayerAction::Throw(item, target) => {
+            actions::attempt_to_throw(entity, item, target, world)
+        }
+        player_actions::PlayerAction::Travel(destination) => {
+            world
+                .entity_mut(entity)
+                .insert(Travelling::new(destination));
+            travel(entity, world)
+        }
+    }
+}
+
+fn travel(entity: Entity, world: &mut World) -> bool {
+    let destination = world.get::<Travelling>(entity).unwrap().destination;
+    let coordinates = world.get::<GridPosition>(entity).unwrap().coordinates;
+
+    let next_step = utils::find_path(coordinates, destination, world)
+        .and_then(|path| path.first().copied())
+        .filter(|next_step| *next_step != coordinates);
+    let interrupted = !utils::get_visible_enemies(entity, world).is_empty();
+
+    let (Some(next_step), false) = (next_step, interrupted) else {
+        world.entity_mut(entity).remove::<Travelling>();
+        return false;
+    };
+
+    let direction = coordinates.direction_to(&next_step).unwrap();
+    let acted = actions::attempt_to_open_door(entity, next_step, world)
+        || actions::attempt_to_move(entity, next_step, direction, actions::MoveType::Walk, world);
+    if !acted {
+        world.entity_mut(entity).remove::<Travelling>();
+    }
+
+    acted
+}
+
+fn rest(entity: Entity, world: &mut World) -> bool {
+    let turns_left = world.get::<Resting>(entity).unwrap().turns_left;
+    let healed = world
+        .get::<Health>(entity)
+        .is_none_or(|health| health.current >= health.max);
+    let interrupted = !utils::get_visible_enemies(entity, world).is_empty();
+
+    if turns_left == 0 || healed || interrupted {
+        world.entity_mut(entity).remove::<Resting>();
+        return false;
     }
+
+    world.get_mut::<Resting>(entity).unwrap().turns_left -= 1;
+    actions::wait(entity, world)
 }
 
 pub fn ai_behavior(entity: Entity, world: &mut World) {
+    let state = update_ai_state(entity, world);
+
+    let _ = match state {
+        AiState::Sleeping => actions::wait(entity, world),
+        AiState::Wandering => {
+            attack_adjacent_enemy(entity, world)
+                || follow_leader(entity, world)
+                || wander(entity, None, world)
+        }
+        AiState::Hunting { target } | AiState::Investigating { target } => {
+            attack_adjacent_enemy(entity, world)
+                || shoot_at_enemy(entity, world)
+                || step_towards(entity, target, world)
+                || wander(entity, Some(target), world)
+        }
+        AiState::Fleeing { from } => {
+            step_away(entity, from, world)
+                || attack_adjacent_enemy(entity, world)
+                || actions::wait(entity, world)
+        }
+        AiState::Searching { around, .. } => {
+            attack_adjacent_enemy(entity, world) || wander(entity, Some(around), world)
+        }
+    };
+}
+
+fn update_ai_state(entity: Entity, world: &mut World) -> AiState {
+    let Some(ai) = world.get::<Ai>(entity) else {
+        return AiState::Wandering;
+    };
+    let (kind, state) = (ai.kind, ai.state);
+
+    let coordinates = world.get::<GridPosition>(entity).unwrap().coordinates;
+    let enemy = utils::get_visible_enemy(entity, world).map(|(_, enemy)| enemy);
+    let provoked = world
+        .get::<Grudges>(entity)
+        .is_some_and(|grudges| !grudges.is_empty());
+    let low_health = match (kind.flee_threshold(), world.get::<Health>(entity)) {
+        (Some(threshold), Some(health)) => health.fraction() <= threshold,
+        _ => false,
+    };
+    let afraid = world
+        .get::<StatusEffects>(entity)
+        .is_some_and(|status_effects| status_effects.has(StatusEffectKind::Fear));
+
+    let new_state = match (state, enemy) {
+        (AiState::Sleeping, Some(enemy)) if provoked || coordinates.distance_max(&enemy) <= 1 => {
+            AiState::Hunting { target: enemy }
+        }
+        (AiState::Sleeping, None) if provoked => AiState::Wandering,
+        (AiState::Sleeping, _) => AiState::Sleeping,
+        (_, Some(enemy)) if low_health || afraid => AiState::Fleeing { from: enemy },
+        (_, Some(enemy)) => AiState::Hunting { target: enemy },
+        (AiState::Hunting { target } | AiState::Investigating { target }, None)
+            if coordinates.distance_max(&target) <= 1 =>
+        {
+            AiState::Searching {
+                around: target,
+                turns_left: SEARCH_TURNS,
+            }
+        }
+        (AiState::Fleeing { .. }, None) => AiState::Searching {
+            around: coordinates,
+            turns_left: SEARCH_TURNS,
+        },
+        (AiState::Searching { turns_left: 0, .. }, None) => AiState::Wandering,
+        (AiState::Searching { around, turns_left }, None) => AiState::Searching {
+            around,
+            turns_left: turns_left - 1,
+        },
+        (state, None) => state,
+    };
+
+    if new_state != state {
+        world.get_mut::<Ai>(entity).unwrap().state = new_state;
+    }
+
+    new_state
+}
+
+fn attack_adjacent_enemy(entity: Entity, world: &mut World) -> bool {
+    let coordinates = world.get::<GridPosition>(entity).unwrap().coordinates;
+
+    let Some(target_coordinates) = GridDirection::ALL
+        .iter()
+        .map(|direction| coordinates + GridVector::from_direction(direction))
+        .find(|target_coordinates| {
+            utils::get_character_at(*target_coordinates, world)
+                .is_some_and(|target_entity| utils::is_hostile(entity, target_entity, world))
+        })
+    else {
+        return false;
+    };
+
+    actions::attempt_to_attack(entity, target_coordinates, world)
+}
+
+fn shoot_at_enemy(entity: Entity, world: &mut World) -> bool {
+    let Some(ranged_stats) = utils::get_ranged_stats(entity, world) else {
+        return false;
+    };
+
+    let Some((enemy, enemy_coordinates)) = utils::get_visible_enemy(entity, world) else {
+        return false;
+    };
+
+    let coordinates = world.get::<GridPosition>(entity).unwrap().coordinates;
+    let (_, hit_entity) =
+        utils::trace_projectile(coordinates, enemy_coordinates, ranged_stats.range, world);
+    if hit_entity != Some(enemy) {
+        return false;
+    }
+
+    actions::attempt_to_shoot(entity, enemy_coordinates, world)
+}
+
+fn follow_leader(entity: Entity, world: &mut World) -> bool {
+    let Some(follower) = world.get::<Follower>(entity) else {
+        return false;
+    };
+
+    let Some(leader_coordinates) = world
+        .get::<GridPosition>(follower.leader)
+        .map(|position| position.coordinates)
+    else {
+        return false;
+    };
+
+    let coordinates = world.get::<GridPosition>(entity).unwrap().coordinates;
+    if coordinates.distance_max(&leader_coordinates) <= 2 {
+        return false;
+    }
+
+    step_towards(entity, leader_coordinates, world)
+}
+
+fn step_towards(entity: Entity, target: GridVector, world: &mut World) -> bool {
+    let coordinates = world.get::<GridPosition>(entity).unwrap().coordinates;
+
+    let Some(direction) = coordinates.direction_to(&target) else {
+        return false;
+    };
+    let direction = utils::confuse_direction(entity, direction, world);
+
+    [
+        direction,
+        direction.rotate_clockwise_45(),
+        direction.rotate_counterclockwise_45(),
+    ]
+    .into_iter()
+    .any(|direction| {
+        let target_coordinates = coordinates + GridVector::from_direction(&direction);
+        actions::attempt_to_open_door(entity, target_coordinates, world)
+            || actions::attempt_to_move(
+                entity,
+                target_coordinates,
+                direction,
+                actions::MoveType::Walk,
+                world,
+            )
+    })
+}
+
+fn step_away(entity: Entity, threat: GridVector, world: &mut World) -> bool {
+    let coordinates = world.get::<GridPosition>(entity).unwrap().coordinates;
+    let current_distance = coordinates.distance_max(&threat);
+
+    let Some((direction, target_coordinates)) = GridDirection::ALL
+        .iter()
+        .map(|direction| {
+            (
+                direction,
+                coordinates + GridVector::from_direction(direction),
+            )
+        })
+        .filter(|(_, target_coordinates)| {
+            target_coordinates.distance_max(&threat) > current_distance
+        })
+        .find(|(_, target_coordinates)| !utils::is_solids_at(*target_coordinates, world))
+    else {
+        return false;
+    };
+
+    actions::attempt_to_move(
+        entity,
+        target_coordinates,
+        direction.to_owned(),
+        actions::MoveType::Walk,
+        world,
+    )
+}
+
+fn wander(entity: Entity, around: Option<GridVector>, world: &mut World) -> bool {
     let mut rng = thread_rng();
 
-    let solids = utils::get_static_solids(world);
     let coordinates = world.get::<GridPosition>(entity).unwrap().coordinates;
 
     let Some((direction, target_coordinates)) = GridDirection::ALL
@@ -53,20 +368,21 @@ pub fn ai_behavior(entity: Entity, world: &mut World) {
                 coordinates + GridVector::from_direction(&direction),
             )
         })
-        .filter(|(_, coordinates)| !solids.contains(coordinates))
+        .filter(|(_, coordinates)| {
+            around.is_none_or(|around| coordinates.distance_max(&around) <= 3)
+        })
+        .filter(|(_, coordinates)| !utils::is_static_solid_at(*coordinates, world))
         .choose(&mut rng)
     else {
-        actions::wait(entity, world);
-        return;
+        return actions::wait(entity, world);
     };
 
-    let _ = actions::attempt_to_attack(entity, target_coordinates, CharacterType::Player, world)
-        || actions::attempt_to_open_door(entity, target_coordinates, world)
+    actions::attempt_to_open_door(entity, target_coordinates, world)
         || actions::attempt_to_move(
             entity,
             target_coordinates,
             direction.to_owned(),
             actions::MoveType::Walk,
             world,
-        );
+        )
 }
@@ -2,6 +2,7 @@ use bevy::asset::AssetContainer;
 use bevy::utils::HashMap;
 use rand::prelude::*;
 
+use crate::game::directions::GridDirection;
 use crate::game::services::game_world::GameWorld;
 use crate::game::vector::GridVector;
 
@@ -32,6 +33,7 @@ pub struct DungeonSettings {
     pub spawn_wall_probability: f32,
     pub spawn_passege_probability: f32,
     pub spawn_door_probability: f32,
+    pub spawn_secret_door_probability: f32,
     pub fill_room_probability: f32,
     pub always_fill_outer_borders: bool,
     pub always_fill_lone_columns: bool,
@@ -47,6 +49,7 @@ enum BorderType {
     Wall,
     Passage,
     Door,
+    SecretDoor,
 }
 
 #[derive(Eq, PartialEq, Hash, Copy, Clone)]
@@ -60,6 +63,47 @@ pub fn create_dungeon(game_world: &mut GameWorld, settings: DungeonSettings, rng
     add_to_world(game_world, dungeon, &settings);
 }
 
+// secret doors are not walkable in `GameWorld`, so the stairs never depend on finding one
+pub fn place_stairs(game_world: &mut GameWorld) {
+    let Some(player) = game_world.player else {
+        return;
+    };
+
+    let stairs = game_world
+        .reachable_from(player)
+        .into_iter()
+        .max_by_key(|vec| (vec.distance_max(&player), vec.x, vec.y));
+
+    if let Some(stairs) = stairs {
+        game_world.add_stairs(stairs);
+    }
+}
+
+// walls facing at least one walkable tile, so a torch on them lights something
+pub fn place_torches(game_world: &mut GameWorld, count: usize, rng: &mut ThreadRng) {
+    let candidates = game_world
+        .walls
+        .iter()
+        .filter(|wall| {
+            GridDirection::ALL
+                .iter()
+                .filter(|direction| direction.is_cardinal())
+                .any(|direction| {
+                    game_world.is_walkable(&(**wall + GridVector::from_direction(direction)))
+                })
+        })
+        .copied()
+        .collect::<Vec<GridVector>>();
+
+    for torch in candidates
+        .choose_multiple(rng, count)
+        .copied()
+        .collect::<Vec<GridVector>>()
+    {
+        game_world.add_torch(torch);
+    }
+}
+
 fn generate_dungeon(settings: &DungeonSettings, rng: &mut ThreadRng) -> Dungeon {
     let mut dungeon = Dungeon::new();
 
@@ -311,7 +355,8 @@ fn select_border_type(rng: &mut ThreadRng, settings: &DungeonSettings) -> Border
     let nothing_probability = (1.0
         - settings.spawn_wall_probability
         - settings.spawn_passege_probability
-        - settings.spawn_door_probability)
+        - settings.spawn_door_probability
+        - settings.spawn_secret_door_probability)
         .min(0.0);
 
     let weights = |border_type: &BorderType| match border_type {
@@ -319,11 +364,13 @@ fn select_border_type(rng: &mut ThreadRng, settings: &DungeonSettings) -> Border
         BorderType::Wall => settings.spawn_wall_probability,
         BorderType::Passage => settings.spawn_passege_probability,
         BorderType::Door => settings.spawn_door_probability,
+        BorderType::SecretDoor => settings.spawn_secret_door_probability,
     };
 
     [
         BorderType::Wall,
         BorderType::Door,
+        BorderType::SecretDoor,
         BorderType::Passage,
         BorderType::Nothing,
     ]
@@ -351,7 +398,7 @@ fn add_border(game_world: &mut GameWorld, border_type: &BorderType, vecs: Vec<Gr
                 game_world.add_floor(vec.to_owned());
             }
         }
-        BorderType::Wall | BorderType::Passage | BorderType::Door => {
+        BorderType::Wall | BorderType::Passage | BorderType::Door | BorderType::SecretDoor => {
             for vec in &vecs {
                 game_world.add_wall(vec.to_owned());
             }
@@ -365,4 +412,8 @@ fn add_border(game_world: &mut GameWorld, border_type: &BorderType, vecs: Vec<Gr
     if border_type == &BorderType::Door {
         game_world.add_door((&vecs[&vecs.len() / 2]).to_owned(), true);
     }
+
+    if border_type == &BorderType::SecretDoor {
+        game_world.add_secret_door(vecs[vecs.len() / 2]);
+    }
 }
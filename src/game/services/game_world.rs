@@ -1,4 +1,9 @@
-use crate::game::services::spawners;
+use std::collections::VecDeque;
+
+use crate::game::components::PlayerControlled;
+use crate::game::directions::GridDirection;
+use crate::game::services::{spawners, utils};
+use crate::game::templates::{ItemKind, MonsterKind, TrapKind};
 use crate::game::vector::GridVector;
 use bevy::prelude::*;
 use bevy::utils::{HashMap, HashSet};
@@ -7,8 +12,13 @@ pub struct GameWorld {
     pub floors: HashSet<GridVector>,
     pub walls: HashSet<GridVector>,
     pub doors: HashMap<GridVector, bool>,
+    pub secret_doors: HashSet<GridVector>,
+    pub stairs: Option<GridVector>,
+    pub torches: HashSet<GridVector>,
     pub player: Option<GridVector>,
-    pub monsters: HashSet<GridVector>,
+    pub monsters: HashMap<GridVector, MonsterKind>,
+    pub items: HashMap<GridVector, ItemKind>,
+    pub traps: HashMap<GridVector, TrapKind>,
 }
 
 impl GameWorld {
@@ -17,8 +27,13 @@ impl GameWorld {
             floors: HashSet::new(),
             walls: HashSet::new(),
             doors: HashMap::new(),
+            secret_doors: HashSet::new(),
+            stairs: None,
+            torches: HashSet::new(),
             player: None,
-            monsters: HashSet::new(),
+            monsters: HashMap::new(),
+            items: HashMap::new(),
+            traps: HashMap::new(),
         }
     }
 
@@ -26,6 +41,11 @@ impl GameWorld {
         self.floors.replace(vec);
         self.walls.remove(&vec);
         self.doors.remove(&vec);
+        self.secret_doors.remove(&vec);
+        if self.stairs == Some(vec) {
+            self.stairs = None;
+        }
+        self.torches.remove(&vec);
         match self.player {
             Some(player_vec) if player_vec == vec => {
                 self.player = None;
@@ -33,6 +53,8 @@ impl GameWorld {
             _ => {}
         }
         self.monsters.remove(&vec);
+        self.items.remove(&vec);
+        self.traps.remove(&vec);
     }
 
     pub fn add_wall(&mut self, vec: GridVector) {
@@ -45,14 +67,66 @@ impl GameWorld {
         self.doors.insert(vec, close);
     }
 
+    pub fn add_secret_door(&mut self, vec: GridVector) {
+        self.add_door(vec, true);
+        self.secret_doors.insert(vec);
+    }
+
+    pub fn add_stairs(&mut self, vec: GridVector) {
+        self.add_floor(vec);
+        self.stairs = Some(vec);
+    }
+
+    // torches hang on walls that are already there
+    pub fn add_torch(&mut self, vec: GridVector) {
+        if self.walls.contains(&vec) {
+            self.torches.insert(vec);
+        }
+    }
+
     pub fn add_player(&mut self, vec: GridVector) {
         self.add_floor(vec);
         self.player = Some(vec);
     }
 
-    pub fn add_monster(&mut self, vec: GridVector) {
+    pub fn add_monster(&mut self, vec: GridVector, kind: MonsterKind) {
         self.add_floor(vec);
-        self.monsters.replace(vec);
+        self.monsters.insert(vec, kind);
+    }
+
+    pub fn add_item(&mut self, vec: GridVector, kind: ItemKind) {
+        self.add_floor(vec);
+        self.items.insert(vec, kind);
+    }
+
+    pub fn add_trap(&mut self, vec: GridVector, kind: TrapKind) {
+        self.add_floor(vec);
+        self.traps.insert(vec, kind);
+    }
+
+    pub fn is_walkable(&self, vec: &GridVector) -> bool {
+        self.floors.contains(vec) && !self.walls.contains(vec) && !self.secret_doors.contains(vec)
+    }
+
+    pub fn reachable_from(&self, start: GridVector) -> HashSet<GridVector> {
+        let mut reachable = HashSet::new();
+        let mut queue = VecDeque::new();
+
+        if self.is_walkable(&start) {
+            reachable.insert(start);
+            queue.push_back(start);
+        }
+
+        while let Some(vec) = queue.pop_front() {
+            for direction in GridDirection::ALL {
+                let next = vec + GridVector::from_direction(&direction);
+                if self.is_walkable(&next) && reachable.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        reachable
     }
 
     pub fn spawn_world(&self, world: &mut World) {
@@ -65,15 +139,37 @@ impl GameWorld {
         }
 
         for (coordinates, closed) in &self.doors {
-            spawners::spawn_door(coordinates.to_owned(), *closed, world);
+            let secret = self.secret_doors.contains(coordinates);
+            spawners::spawn_door(coordinates.to_owned(), *closed, secret, world);
+        }
+
+        if let Some(coordinates) = &self.stairs {
+            spawners::spawn_stairs(coordinates.to_owned(), world);
+        }
+
+        for coordinates in &self.torches {
+            spawners::spawn_torch(coordinates.to_owned(), world);
         }
 
         if let Some(coordinates) = &self.player {
-            spawners::spawn_player(coordinates.to_owned(), 0.0, world);
+            let mut query = world.query_filtered::<Entity, With<PlayerControlled>>();
+            if let Ok(player) = query.get_single(world) {
+                utils::set_coordinates(player, coordinates.to_owned(), world);
+            } else {
+                spawners::spawn_player(coordinates.to_owned(), 0.0, world);
+            }
+        }
+
+        for (coordinates, kind) in &self.monsters {
+            spawners::spawn_monster(coordinates.to_owned(), *kind, 0.1, world);
+        }
+
+        for (coordinates, kind) in &self.items {
+            spawners::spawn_item(coordinates.to_owned(), *kind, world);
         }
 
-        for coordinates in &self.monsters {
-            spawners::spawn_monster(coordinates.to_owned(), 0.1, world);
+        for (coordinates, kind) in &self.traps {
+            spawners::spawn_trap(coordinates.to_owned(), *kind, world);
         }
     }
 }
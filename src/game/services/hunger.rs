@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+use crate::game::components::*;
+use crate::game::resources::HungerSettings;
+use crate::game::services::actions;
+
+pub fn tick_hunger(elapsed: f32, world: &mut World) {
+    let settings = world.resource::<HungerSettings>();
+    if !settings.enabled {
+        return;
+    }
+    let amount = elapsed * settings.rate;
+
+    let mut starvation_damage = Vec::new();
+    let mut query = world.query::<(Entity, &mut Satiation, Has<PlayerControlled>)>();
+    for (entity, mut satiation, is_player) in query.iter_mut(world) {
+        let previous_state = satiation.state();
+        let starved_ticks = satiation.consume(amount);
+        let state = satiation.state();
+
+        if is_player && state != previous_state {
+            info!("{}", state.warning());
+        }
+        if starved_ticks > 0 {
+            starvation_damage.push((entity, starved_ticks));
+        }
+    }
+
+    for (entity, damage) in starvation_damage {
+        actions::apply_damage(entity, damage, world);
+    }
+}
+
+pub fn feed(entity: Entity, amount: f32, world: &mut World) {
+    let Some(mut satiation) = world.get_mut::<Satiation>(entity) else {
+        return;
+    };
+
+    let previous_state = satiation.state();
+    satiation.restore(amount);
+    if satiation.state() != previous_state {
+        info!("{}", satiation.state().warning());
+    }
+}
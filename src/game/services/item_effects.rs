@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use crate::game::components::*;
+use crate::game::model::{StatusEffect, StatusEffectKind};
+use crate::game::services::{actions, hunger, utils, visibility};
+use crate::game::templates::UseEffect;
+use crate::game::vector::GridVector;
+
+pub fn apply_use_effect(entity: Entity, effect: UseEffect, world: &mut World) {
+    match effect {
+        UseEffect::Heal(amount) => actions::heal(entity, amount, world),
+        UseEffect::Feed(amount) => hunger::feed(entity, amount, world),
+        UseEffect::Teleport => teleport(entity, world),
+        UseEffect::MagicMapping => visibility::reveal_map(world),
+        UseEffect::Fear { radius, duration } => frighten(entity, radius, duration, world),
+        UseEffect::Status(effect) => {
+            if let Some(mut status_effects) = world.get_mut::<StatusEffects>(entity) {
+                status_effects.add(effect);
+            }
+        }
+    }
+}
+
+pub fn teleport(entity: Entity, world: &mut World) {
+    let coordinates = world.get::<GridPosition>(entity).unwrap().coordinates;
+
+    let destinations = utils::get_reachable_floors(coordinates, world)
+        .into_iter()
+        .filter(|destination| {
+            !utils::is_solids_at(*destination, world)
+                && utils::get_character_at(*destination, world).is_none()
+        })
+        .collect::<Vec<GridVector>>();
+
+    if let Some(destination) = destinations.choose(&mut thread_rng()) {
+        utils::set_coordinates(entity, *destination, world);
+    }
+}
+
+fn frighten(entity: Entity, radius: i32, duration: f32, world: &mut World) {
+    let coordinates = world.get::<GridPosition>(entity).unwrap().coordinates;
+
+    let mut query = world.query_filtered::<(Entity, &GridPosition), With<Ai>>();
+    let targets = query
+        .iter(world)
+        .filter(|(_, position)| coordinates.distance_max(&position.coordinates) <= radius)
+        .map(|(target, _)| target)
+        .collect::<Vec<Entity>>();
+
+    for target in targets {
+        if !utils::is_hostile(entity, target, world) {
+            continue;
+        }
+
+        if let Some(mut status_effects) = world.get_mut::<StatusEffects>(target) {
+            status_effects.add(StatusEffect::new(StatusEffectKind::Fear, duration, 1));
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+
+use crate::game::components::*;
+use crate::game::resources::{DungeonDepth, ExploredTiles, SpatialIndex, VisibleTiles};
+use crate::game::services::game_world::GameWorld;
+use crate::game::services::{dungeon_generator, populator, utils};
+use crate::game::vector::GridVector;
+
+pub fn create_level(world: &mut World) {
+    info!("create_level");
+
+    let mut rng = rand::thread_rng();
+
+    let rooms_horizontal = 9;
+    let rooms_vertical = 9;
+
+    let mut game_world = GameWorld::empty();
+
+    let settings = dungeon_generator::DungeonSettings {
+        anchor: GridVector::zero(),
+        rooms_horizontal,
+        rooms_vertical,
+        rooms_width: 3,
+        rooms_height: 3,
+        spawn_wall_probability: 0.25,
+        spawn_passege_probability: 0.25,
+        spawn_door_probability: 0.25,
+        spawn_secret_door_probability: 0.05,
+        fill_room_probability: 0.5,
+        always_fill_outer_borders: true,
+        always_fill_lone_columns: false,
+        always_fill_closed_rooms: true,
+    };
+
+    dungeon_generator::create_dungeon(&mut game_world, settings, &mut rng);
+
+    game_world.add_player(GridVector::new(
+        4 * (rooms_horizontal as i32 / 2) + 2,
+        4 * (rooms_vertical as i32 / 2) + 2,
+    ));
+    dungeon_generator::place_stairs(&mut game_world);
+    dungeon_generator::place_torches(&mut game_world, 12, &mut rng);
+
+    let population_settings = populator::PopulationSettings {
+        depth: world.resource::<DungeonDepth>().0,
+        monster_base_budget: 4,
+        monster_budget_per_depth: 3,
+        item_base_budget: 3,
+        item_budget_per_depth: 1,
+        trap_base_budget: 1,
+        trap_budget_per_depth: 1,
+        min_player_distance: 6,
+    };
+
+    populator::populate(&mut game_world, population_settings, &mut rng);
+
+    game_world.spawn_world(world);
+}
+
+pub fn descend(world: &mut World) {
+    // everything on the map goes, carried items and the player stay
+    let mut query = world.query_filtered::<Entity, (
+        With<MapEntity>,
+        With<GridPosition>,
+        Without<PlayerControlled>,
+    )>();
+    let entities = query.iter(world).collect::<Vec<Entity>>();
+    for entity in entities {
+        utils::despawn(entity, world);
+    }
+
+    world.resource_mut::<ExploredTiles>().clear();
+    world.resource_mut::<VisibleTiles>().clear();
+    world.resource_mut::<DungeonDepth>().0 += 1;
+    info!(
+        "you descend to depth {}",
+        world.resource::<DungeonDepth>().0
+    );
+
+    create_level(world);
+
+    // the rest of this turn already runs on the new level
+    let mut query = world.query::<(Entity, &GridPosition)>();
+    let positions = query
+        .iter(world)
+        .map(|(entity, position)| (entity, position.coordinates))
+        .collect::<Vec<(Entity, GridVector)>>();
+    let mut spatial_index = world.resource_mut::<SpatialIndex>();
+    for (entity, coordinates) in positions {
+        spatial_index.insert(entity, coordinates);
+    }
+}
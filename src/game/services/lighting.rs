@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+use crate::game::components::*;
+use crate::game::resources::LightMap;
+use crate::game::services::utils;
+use crate::game::vector::GridVector;
+
+// light everywhere gets, even away from any source
+pub const AMBIENT_LIGHT: f32 = 0.1;
+// below this, characters can only be made out right next to the viewer
+const DARKNESS_THRESHOLD: f32 = 0.25;
+
+pub fn update_light_map(world: &mut World) {
+    let mut query = world.query::<(&GridPosition, &LightSource)>();
+    let sources = query
+        .iter(world)
+        .map(|(position, light)| (position.coordinates, *light))
+        .collect::<Vec<(GridVector, LightSource)>>();
+
+    let mut light_map = LightMap::default();
+    for (origin, light) in sources {
+        for y in -light.radius..=light.radius {
+            for x in -light.radius..=light.radius {
+                let coordinates = origin + GridVector::new(x, y);
+                if !utils::has_line_of_sight(origin, coordinates, world) {
+                    continue;
+                }
+
+                let intensity = light.intensity_at(origin.distance_max(&coordinates));
+                let tile = light_map.entry(coordinates).or_insert(Color::BLACK);
+                *tile += light.color * intensity;
+            }
+        }
+    }
+
+    *world.resource_mut::<LightMap>() = light_map;
+}
+
+pub fn get_light_level(coordinates: GridVector, light_map: &LightMap) -> f32 {
+    let light = light_map
+        .get(&coordinates)
+        .map_or(0.0, |color| color.r().max(color.g()).max(color.b()));
+    (AMBIENT_LIGHT + light).min(1.0)
+}
+
+// only characters hide in the dark, the map itself is seen by line of sight alone
+pub fn is_lit_for(viewer: GridVector, coordinates: GridVector, light_map: &LightMap) -> bool {
+    viewer.distance_max(&coordinates) <= 1
+        || get_light_level(coordinates, light_map) >= DARKNESS_THRESHOLD
+}
@@ -2,6 +2,17 @@ mod actions;
 pub mod behaviors;
 pub mod dungeon_generator;
 pub mod game_world;
+pub mod hunger;
+mod item_effects;
+pub mod levels;
+pub mod lighting;
+pub mod noise;
 mod player_actions;
+pub mod populator;
+mod progression;
 pub mod spawners;
+pub mod status_effects;
+mod targeting;
+mod traps;
 mod utils;
+pub mod visibility;
@@ -0,0 +1,73 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::game::components::*;
+use crate::game::directions::GridDirection;
+use crate::game::model::AiState;
+use crate::game::services::utils;
+use crate::game::vector::GridVector;
+
+pub fn emit_noise(origin: GridVector, radius: i32, source: Option<Entity>, world: &mut World) {
+    if radius <= 0 {
+        return;
+    }
+
+    let heard = propagate(origin, radius, world);
+
+    let mut query = world.query_filtered::<(Entity, &GridPosition), With<Ai>>();
+    let listeners = query
+        .iter(world)
+        .filter(|(entity, position)| {
+            Some(*entity) != source && heard.contains(&position.coordinates)
+        })
+        .map(|(entity, _)| entity)
+        .collect::<Vec<Entity>>();
+
+    for listener in listeners {
+        if source.is_some_and(|source| !utils::is_hostile(listener, source, world)) {
+            continue;
+        }
+
+        hear_noise(listener, origin, world);
+    }
+}
+
+fn propagate(origin: GridVector, radius: i32, world: &mut World) -> HashSet<GridVector> {
+    let mut heard = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    heard.insert(origin);
+    queue.push_back((origin, 0));
+
+    while let Some((coordinates, distance)) = queue.pop_front() {
+        if distance >= radius {
+            continue;
+        }
+
+        for direction in GridDirection::ALL {
+            let next = coordinates + GridVector::from_direction(&direction);
+            if !heard.contains(&next) && !utils::is_opaque_at(next, world) {
+                heard.insert(next);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    heard
+}
+
+fn hear_noise(entity: Entity, origin: GridVector, world: &mut World) {
+    let mut ai = world.get_mut::<Ai>(entity).unwrap();
+
+    match ai.state {
+        AiState::Sleeping
+        | AiState::Wandering
+        | AiState::Investigating { .. }
+        | AiState::Searching { .. } => {
+            ai.state = AiState::Investigating { target: origin };
+        }
+        AiState::Hunting { .. } | AiState::Fleeing { .. } => {}
+    }
+}
@@ -1,34 +1,164 @@
 use bevy::prelude::*;
 
 use crate::game::directions::GridDirection;
+use crate::game::resources::{PerkChoice, Targeting, TargetingPurpose};
+use crate::game::services::targeting;
+use crate::game::vector::GridVector;
 
 pub enum PlayerAction {
     Wait,
+    Rest,
+    Search,
+    Descend,
     MoveAttack(GridDirection),
     Run(GridDirection),
+    Tame(GridDirection),
+    Disarm(GridDirection),
+    ToggleSneak,
+    PickUp,
+    StartTargeting(TargetingPurpose),
+    AimThrow(usize),
+    Use(usize),
+    ToggleEquipment(usize),
+    ChoosePerk(usize),
+    Fire(GridVector),
+    Throw(Entity, GridVector),
+    Travel(GridVector),
 }
 
 pub fn get_player_action(world: &mut World) -> Option<PlayerAction> {
+    if world.contains_resource::<PerkChoice>() {
+        let key_input = world.resource::<Input<KeyCode>>();
+        return get_slot_from_input(key_input).map(PlayerAction::ChoosePerk);
+    }
+
+    if world.contains_resource::<Targeting>() {
+        return get_targeting_action(world);
+    }
+
     let key_input = world.resource::<Input<KeyCode>>();
 
     if key_input.pressed(KeyCode::Numpad5) {
         return Some(PlayerAction::Wait);
     }
 
-    if let Some(direction) = get_direction_from_input(key_input) {
+    if key_input.just_pressed(KeyCode::R) {
+        return Some(PlayerAction::Rest);
+    }
+
+    if key_input.just_pressed(KeyCode::S) {
+        return Some(PlayerAction::Search);
+    }
+
+    if key_input.just_pressed(KeyCode::D) {
+        return Some(PlayerAction::Descend);
+    }
+
+    if key_input.just_pressed(KeyCode::N) {
+        return Some(PlayerAction::ToggleSneak);
+    }
+
+    if key_input.just_pressed(KeyCode::G) {
+        return Some(PlayerAction::PickUp);
+    }
+
+    if key_input.just_pressed(KeyCode::F) {
+        return Some(PlayerAction::StartTargeting(TargetingPurpose::Fire));
+    }
+
+    if key_input.just_pressed(KeyCode::L) {
+        return Some(PlayerAction::StartTargeting(TargetingPurpose::Look));
+    }
+
+    if key_input.just_pressed(KeyCode::Period) {
+        return Some(PlayerAction::StartTargeting(TargetingPurpose::Travel));
+    }
+
+    if key_input.pressed(KeyCode::T) {
+        if let Some(slot) = get_slot_from_input(key_input) {
+            return Some(PlayerAction::AimThrow(slot));
+        }
+    }
+
+    if key_input.pressed(KeyCode::U) {
+        if let Some(slot) = get_slot_from_input(key_input) {
+            return Some(PlayerAction::Use(slot));
+        }
+    }
+
+    if key_input.pressed(KeyCode::E) {
+        if let Some(slot) = get_slot_from_input(key_input) {
+            return Some(PlayerAction::ToggleEquipment(slot));
+        }
+    }
+
+    if let Some(direction) = get_direction_from_input(|keys| key_input.any_pressed(keys)) {
         if key_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
             return Some(PlayerAction::Run(direction));
         }
 
+        if key_input.pressed(KeyCode::C) {
+            return Some(PlayerAction::Tame(direction));
+        }
+
+        if key_input.pressed(KeyCode::X) {
+            return Some(PlayerAction::Disarm(direction));
+        }
+
         Some(PlayerAction::MoveAttack(direction))
     } else {
         None
     }
 }
 
-fn get_direction_from_input(key_input: &Input<KeyCode>) -> Option<GridDirection> {
-    let any_pressed = |inputs: Vec<KeyCode>| key_input.any_pressed(inputs);
+fn get_targeting_action(world: &mut World) -> Option<PlayerAction> {
+    let key_input = world.resource::<Input<KeyCode>>();
+    let cancel = key_input.just_pressed(KeyCode::Back);
+    let confirm = key_input.any_just_pressed([KeyCode::Return, KeyCode::NumpadEnter, KeyCode::F]);
+    let cycle = key_input.just_pressed(KeyCode::Tab);
+    let direction = get_direction_from_input(|keys| key_input.any_just_pressed(keys));
+
+    if cancel {
+        targeting::cancel_targeting(world);
+        None
+    } else if confirm {
+        let targeting = targeting::confirm_targeting(world)?;
+        match targeting.purpose {
+            TargetingPurpose::Look => None,
+            TargetingPurpose::Fire => Some(PlayerAction::Fire(targeting.cursor)),
+            TargetingPurpose::Throw(item) => Some(PlayerAction::Throw(item, targeting.cursor)),
+            TargetingPurpose::Travel => Some(PlayerAction::Travel(targeting.cursor)),
+        }
+    } else if cycle {
+        targeting::cycle_target(world);
+        None
+    } else if let Some(direction) = direction {
+        targeting::move_cursor(direction, world);
+        None
+    } else {
+        None
+    }
+}
+
+fn get_slot_from_input(key_input: &Input<KeyCode>) -> Option<usize> {
+    [
+        KeyCode::Key1,
+        KeyCode::Key2,
+        KeyCode::Key3,
+        KeyCode::Key4,
+        KeyCode::Key5,
+        KeyCode::Key6,
+        KeyCode::Key7,
+        KeyCode::Key8,
+        KeyCode::Key9,
+    ]
+    .into_iter()
+    .position(|key| key_input.just_pressed(key))
+}
 
+fn get_direction_from_input(
+    mut any_pressed: impl FnMut(Vec<KeyCode>) -> bool,
+) -> Option<GridDirection> {
     if any_pressed(vec![KeyCode::Numpad8, KeyCode::Up]) {
         Some(GridDirection::North)
     } else if any_pressed(vec![KeyCode::Numpad6, KeyCode::Right]) {
@@ -0,0 +1,89 @@
+use rand::prelude::*;
+
+use crate::game::services::game_world::GameWorld;
+use crate::game::templates::{SpawnEntry, ITEM_SPAWN_TABLE, MONSTER_SPAWN_TABLE, TRAP_SPAWN_TABLE};
+use crate::game::vector::GridVector;
+
+pub struct PopulationSettings {
+    pub depth: u32,
+    pub monster_base_budget: u32,
+    pub monster_budget_per_depth: u32,
+    pub item_base_budget: u32,
+    pub item_budget_per_depth: u32,
+    pub trap_base_budget: u32,
+    pub trap_budget_per_depth: u32,
+    pub min_player_distance: i32,
+}
+
+impl PopulationSettings {
+    fn monster_budget(&self) -> u32 {
+        self.monster_base_budget + self.monster_budget_per_depth * self.depth
+    }
+
+    fn item_budget(&self) -> u32 {
+        self.item_base_budget + self.item_budget_per_depth * self.depth
+    }
+
+    fn trap_budget(&self) -> u32 {
+        self.trap_base_budget + self.trap_budget_per_depth * self.depth
+    }
+}
+
+pub fn populate(game_world: &mut GameWorld, settings: PopulationSettings, rng: &mut ThreadRng) {
+    let Some(player) = game_world.player else {
+        return;
+    };
+
+    let mut free_tiles = game_world
+        .reachable_from(player)
+        .into_iter()
+        .filter(|vec| vec.distance_max(&player) >= settings.min_player_distance)
+        .filter(|vec| !game_world.doors.contains_key(vec))
+        .filter(|vec| game_world.stairs != Some(*vec))
+        .collect::<Vec<GridVector>>();
+    free_tiles.shuffle(rng);
+
+    let mut budget = settings.monster_budget();
+    while let Some(entry) = select_entry(&MONSTER_SPAWN_TABLE, settings.depth, budget, rng) {
+        let Some(vec) = free_tiles.pop() else {
+            break;
+        };
+        game_world.add_monster(vec, entry.kind);
+        budget -= entry.cost;
+    }
+
+    let mut budget = settings.item_budget();
+    while let Some(entry) = select_entry(&ITEM_SPAWN_TABLE, settings.depth, budget, rng) {
+        let Some(vec) = free_tiles.pop() else {
+            break;
+        };
+        game_world.add_item(vec, entry.kind);
+        budget -= entry.cost;
+    }
+
+    let mut budget = settings.trap_budget();
+    while let Some(entry) = select_entry(&TRAP_SPAWN_TABLE, settings.depth, budget, rng) {
+        let Some(vec) = free_tiles.pop() else {
+            break;
+        };
+        game_world.add_trap(vec, entry.kind);
+        budget -= entry.cost;
+    }
+}
+
+fn select_entry<'a, T>(
+    table: &'a [SpawnEntry<T>],
+    depth: u32,
+    budget: u32,
+    rng: &mut ThreadRng,
+) -> Option<&'a SpawnEntry<T>> {
+    let candidates = table
+        .iter()
+        .filter(|entry| entry.min_depth <= depth && entry.cost <= budget)
+        .collect::<Vec<&SpawnEntry<T>>>();
+
+    candidates
+        .choose_weighted(rng, |entry| entry.weight)
+        .ok()
+        .copied()
+}
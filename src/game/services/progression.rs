@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use crate::game::components::*;
+use crate::game::model::Perk;
+use crate::game::resources::PerkChoice;
+
+const HEALTH_PER_LEVEL: i32 = 3;
+const ATTACK_PER_LEVEL: i32 = 1;
+const PERK_OPTIONS: usize = 3;
+
+pub fn gain_experience(entity: Entity, points: u32, world: &mut World) {
+    let Some(mut experience) = world.get_mut::<Experience>(entity) else {
+        return;
+    };
+
+    experience.points += points;
+    let mut levels_gained = 0;
+    while experience.points >= experience.next_level_points() {
+        experience.level += 1;
+        experience.unspent_perks += 1;
+        levels_gained += 1;
+    }
+    let level = experience.level;
+
+    for _ in 0..levels_gained {
+        if let Some(mut health) = world.get_mut::<Health>(entity) {
+            health.max += HEALTH_PER_LEVEL;
+            health.current += HEALTH_PER_LEVEL;
+        }
+        if let Some(mut stats) = world.get_mut::<CombatStats>(entity) {
+            stats.attack += ATTACK_PER_LEVEL;
+        }
+    }
+
+    if levels_gained > 0 {
+        info!("level up! you are now level {}", level);
+        offer_perks(entity, world);
+    }
+}
+
+fn offer_perks(entity: Entity, world: &mut World) {
+    let unspent_perks = world
+        .get::<Experience>(entity)
+        .map_or(0, |experience| experience.unspent_perks);
+    if unspent_perks == 0 || world.contains_resource::<PerkChoice>() {
+        return;
+    }
+
+    let options = Perk::ALL
+        .choose_multiple(&mut thread_rng(), PERK_OPTIONS)
+        .copied()
+        .collect::<Vec<Perk>>();
+    for (index, perk) in options.iter().enumerate() {
+        info!("{}: {}", index + 1, perk.label());
+    }
+
+    world.insert_resource(PerkChoice { entity, options });
+}
+
+pub fn choose_perk(index: usize, world: &mut World) {
+    let Some(perk) = world
+        .get_resource::<PerkChoice>()
+        .and_then(|choice| choice.options.get(index).copied())
+    else {
+        return;
+    };
+    let entity = world.remove_resource::<PerkChoice>().unwrap().entity;
+
+    match perk {
+        Perk::Toughness => {
+            if let Some(mut health) = world.get_mut::<Health>(entity) {
+                health.max += 5;
+                health.current += 5;
+            }
+        }
+        Perk::Might => {
+            if let Some(mut stats) = world.get_mut::<CombatStats>(entity) {
+                stats.attack += 1;
+            }
+        }
+        Perk::Guard => {
+            if let Some(mut stats) = world.get_mut::<CombatStats>(entity) {
+                stats.defense += 1;
+            }
+        }
+        Perk::KeenEyes => {
+            if let Some(mut vision) = world.get_mut::<Vision>(entity) {
+                vision.radius += 1;
+            }
+        }
+    }
+    info!("you gain {}", perk.label());
+
+    if let Some(mut experience) = world.get_mut::<Experience>(entity) {
+        experience.unspent_perks -= 1;
+    }
+    offer_perks(entity, world);
+}
@@ -2,13 +2,16 @@ use bevy::prelude::*;
 
 use crate::game::components::*;
 use crate::game::directions::GridDirection;
-use crate::game::model::CharacterType;
+use crate::game::resources::HungerSettings;
+use crate::game::templates::{door_sprite_index, ItemKind, MonsterKind, TrapKind};
 use crate::game::vector::GridVector;
 
 pub fn spawn_floor(vec: GridVector, world: &mut World) -> Entity {
     world
         .spawn((
             Name::new("floor"),
+            Floor,
+            Renderable::new(0, 0.0),
             GridPosition {
                 coordinates: vec,
                 direction: None,
@@ -22,6 +25,7 @@ pub fn spawn_wall(vec: GridVector, world: &mut World) -> Entity {
     world
         .spawn((
             Name::new("wall"),
+            Renderable::new(1, 10.0),
             GridPosition {
                 coordinates: vec,
                 direction: None,
@@ -32,11 +36,18 @@ pub fn spawn_wall(vec: GridVector, world: &mut World) -> Entity {
         .id()
 }
 
-pub fn spawn_door(vec: GridVector, closed: bool, world: &mut World) -> Entity {
+pub fn spawn_door(vec: GridVector, closed: bool, secret: bool, world: &mut World) -> Entity {
+    let door = if secret {
+        Door::secret()
+    } else {
+        Door::new(closed)
+    };
+
     world
         .spawn((
             Name::new("door"),
-            Door::new(closed),
+            Renderable::new(door_sprite_index(&door), 10.0),
+            door,
             GridPosition {
                 coordinates: vec,
                 direction: None,
@@ -47,11 +58,58 @@ pub fn spawn_door(vec: GridVector, closed: bool, world: &mut World) -> Entity {
         .id()
 }
 
+pub fn spawn_stairs(vec: GridVector, world: &mut World) -> Entity {
+    world
+        .spawn((
+            Name::new("stairs"),
+            Renderable::new(5, 1.0),
+            Stairs,
+            GridPosition {
+                coordinates: vec,
+                direction: None,
+            },
+            MapEntity,
+        ))
+        .id()
+}
+
+pub fn spawn_torch(vec: GridVector, world: &mut World) -> Entity {
+    world
+        .spawn((
+            Name::new("torch"),
+            Renderable::new(28, 11.0),
+            Torch,
+            LightSource::TORCH,
+            GridPosition {
+                coordinates: vec,
+                direction: None,
+            },
+            MapEntity,
+        ))
+        .id()
+}
+
 pub fn spawn_player(vec: GridVector, cooldown: f32, world: &mut World) -> Entity {
+    let max_satiation = world.resource::<HungerSettings>().max_satiation;
+
     world
         .spawn((
             Name::new("player"),
-            Character::new(CharacterType::Player),
+            Renderable::new(8, 20.0).with_flip(FlipRule::FaceDirection),
+            Character,
+            Faction::Player,
+            Grudges::default(),
+            (
+                Health::new(20),
+                CombatStats::new(4, 1),
+                Experience::default(),
+                Satiation::new(max_satiation),
+                NaturalRegeneration::new(10.0),
+            ),
+            (Vision::new(8), LightSource::LANTERN),
+            StatusEffects::default(),
+            Inventory::default(),
+            Equipment::default(),
             GridPosition {
                 coordinates: vec,
                 direction: Some(GridDirection::North),
@@ -64,18 +122,94 @@ pub fn spawn_player(vec: GridVector, cooldown: f32, world: &mut World) -> Entity
         .id()
 }
 
-pub fn spawn_monster(vec: GridVector, cooldown: f32, world: &mut World) -> Entity {
+pub fn spawn_monster(
+    vec: GridVector,
+    kind: MonsterKind,
+    cooldown: f32,
+    world: &mut World,
+) -> Entity {
+    let template = kind.template();
+
+    let mut entity = world.spawn((
+        Name::new("monster"),
+        Renderable::new(template.sprite_index, 20.0).with_flip(FlipRule::FaceDirection),
+        Character,
+        Monster::new(kind),
+        template.faction,
+        Grudges::default(),
+        Health::new(template.max_health),
+        CombatStats::new(template.attack, template.defense),
+        Vision::new(template.sight_radius),
+        Ai::new(template.ai_kind),
+        StatusEffects::default(),
+        GridPosition {
+            coordinates: vec,
+            direction: Some(GridDirection::North),
+        },
+        Solid,
+        MapEntity,
+        Cooldown(cooldown),
+    ));
+
+    if let Some(ranged) = template.ranged {
+        entity.insert(RangedAttack(ranged));
+    }
+    if let Some(light) = template.light {
+        entity.insert(light);
+    }
+    let entity = entity.id();
+
+    let mut inventory = Inventory::default();
+    let mut equipment = Equipment::default();
+    for kind in template.equipment {
+        let item = spawn_carried_item(*kind, world);
+        inventory.push(item);
+        if let Some(slot) = kind.template().class.slot() {
+            equipment.set(slot, Some(item));
+        }
+    }
+    world.entity_mut(entity).insert((inventory, equipment));
+
+    entity
+}
+
+pub fn spawn_carried_item(kind: ItemKind, world: &mut World) -> Entity {
+    world
+        .spawn((
+            Name::new("item"),
+            Renderable::new(kind.template().sprite_index, 5.0),
+            Item::new(kind),
+            MapEntity,
+        ))
+        .id()
+}
+
+pub fn spawn_item(vec: GridVector, kind: ItemKind, world: &mut World) -> Entity {
     world
         .spawn((
-            Name::new("monster"),
-            Character::new(CharacterType::Monster),
+            Name::new("item"),
+            Renderable::new(kind.template().sprite_index, 5.0),
+            Item::new(kind),
             GridPosition {
                 coordinates: vec,
-                direction: Some(GridDirection::North),
+                direction: None,
+            },
+            MapEntity,
+        ))
+        .id()
+}
+
+pub fn spawn_trap(vec: GridVector, kind: TrapKind, world: &mut World) -> Entity {
+    world
+        .spawn((
+            Name::new("trap"),
+            Renderable::new(kind.template().sprite_index, 2.0),
+            Trap::new(kind),
+            GridPosition {
+                coordinates: vec,
+                direction: None,
             },
-            Solid,
             MapEntity,
-            Cooldown(cooldown),
         ))
         .id()
 }
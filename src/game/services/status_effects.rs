@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+use crate::game::components::*;
+use crate::game::model::{HungerState, StatusEffectKind};
+use crate::game::services::actions;
+
+pub fn tick_status_effects(elapsed: f32, world: &mut World) {
+    let mut health_changes = Vec::new();
+
+    let mut query = world.query::<(Entity, &mut StatusEffects)>();
+    for (entity, mut status_effects) in query.iter_mut(world) {
+        if status_effects.is_empty() {
+            continue;
+        }
+
+        for effect in status_effects.iter_mut() {
+            let ticks = effect.advance(elapsed);
+            match effect.kind {
+                StatusEffectKind::Poison => {
+                    health_changes.push((entity, -effect.magnitude * ticks));
+                }
+                StatusEffectKind::Regeneration => {
+                    health_changes.push((entity, effect.magnitude * ticks));
+                }
+                _ => {}
+            }
+        }
+
+        status_effects.retain(|effect| !effect.is_expired());
+    }
+
+    for (entity, amount) in health_changes {
+        if amount < 0 {
+            actions::apply_damage(entity, -amount, world);
+        } else if amount > 0 {
+            actions::heal(entity, amount, world);
+        }
+    }
+}
+
+pub fn tick_natural_regeneration(elapsed: f32, world: &mut World) {
+    let mut query = world.query::<(Entity, &mut NaturalRegeneration, Option<&Satiation>)>();
+    let heals = query
+        .iter_mut(world)
+        .filter(|(_, _, satiation)| {
+            satiation.is_none_or(|satiation| satiation.state() == HungerState::Satiated)
+        })
+        .map(|(entity, mut regeneration, _)| (entity, regeneration.advance(elapsed)))
+        .filter(|(_, amount)| *amount > 0)
+        .collect::<Vec<(Entity, i32)>>();
+
+    for (entity, amount) in heals {
+        actions::heal(entity, amount, world);
+    }
+}
@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+
+use crate::game::components::*;
+use crate::game::directions::GridDirection;
+use crate::game::resources::{Targeting, TargetingPurpose};
+use crate::game::services::{actions, utils};
+use crate::game::vector::GridVector;
+
+pub fn start_targeting(entity: Entity, purpose: TargetingPurpose, world: &mut World) -> bool {
+    let range = match purpose {
+        TargetingPurpose::Fire => match utils::get_ranged_stats(entity, world) {
+            Some(ranged_stats) => ranged_stats.range,
+            None => {
+                info!("nothing to fire with");
+                return false;
+            }
+        },
+        TargetingPurpose::Throw(_) => actions::THROW_RANGE,
+        TargetingPurpose::Look | TargetingPurpose::Travel => 0,
+    };
+
+    let origin = world.get::<GridPosition>(entity).unwrap().coordinates;
+    let candidates = match purpose {
+        TargetingPurpose::Travel => Vec::new(),
+        _ => utils::get_visible_enemies(entity, world)
+            .into_iter()
+            .map(|(enemy, _)| enemy)
+            .collect::<Vec<Entity>>(),
+    };
+
+    world.insert_resource(Targeting {
+        purpose,
+        origin,
+        range,
+        cursor: origin,
+        candidates,
+        candidate_index: 0,
+        path: Vec::new(),
+        description: String::new(),
+    });
+    set_cursor(origin, world);
+    aim_at_candidate(world);
+
+    false
+}
+
+pub fn cycle_target(world: &mut World) {
+    let mut targeting = world.resource_mut::<Targeting>();
+    if targeting.candidates.is_empty() {
+        return;
+    }
+
+    targeting.candidate_index = (targeting.candidate_index + 1) % targeting.candidates.len();
+    aim_at_candidate(world);
+}
+
+pub fn move_cursor(direction: GridDirection, world: &mut World) {
+    let cursor = world.resource::<Targeting>().cursor;
+    set_cursor(cursor + GridVector::from_direction(&direction), world);
+}
+
+pub fn set_cursor(coordinates: GridVector, world: &mut World) {
+    let targeting = world.resource::<Targeting>();
+    let (purpose, origin, range) = (targeting.purpose, targeting.origin, targeting.range);
+
+    let path = match purpose {
+        TargetingPurpose::Look => Vec::new(),
+        TargetingPurpose::Fire | TargetingPurpose::Throw(_) => {
+            utils::trace_projectile(origin, coordinates, range, world).0
+        }
+        TargetingPurpose::Travel => {
+            utils::find_path(origin, coordinates, world).unwrap_or_default()
+        }
+    };
+    let description = utils::describe_tile(coordinates, world);
+
+    let mut targeting = world.resource_mut::<Targeting>();
+    targeting.cursor = coordinates;
+    targeting.path = path;
+    targeting.description = description;
+}
+
+pub fn confirm_targeting(world: &mut World) -> Option<Targeting> {
+    world
+        .remove_resource::<Targeting>()
+        .filter(|targeting| targeting.cursor != targeting.origin)
+}
+
+pub fn cancel_targeting(world: &mut World) {
+    world.remove_resource::<Targeting>();
+}
+
+fn aim_at_candidate(world: &mut World) {
+    let targeting = world.resource::<Targeting>();
+    let Some(candidate) = targeting.candidates.get(targeting.candidate_index).copied() else {
+        return;
+    };
+
+    if let Some(coordinates) = world
+        .get::<GridPosition>(candidate)
+        .map(|position| position.coordinates)
+    {
+        set_cursor(coordinates, world);
+    }
+}
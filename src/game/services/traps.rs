@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use crate::game::components::*;
+use crate::game::directions::GridDirection;
+use crate::game::resources::SpatialIndex;
+use crate::game::services::{actions, item_effects, noise, utils};
+use crate::game::vector::GridVector;
+
+pub fn get_trap_at(coordinates: GridVector, world: &mut World) -> Option<Entity> {
+    world
+        .resource::<SpatialIndex>()
+        .entities_at(&coordinates)
+        .iter()
+        .find(|entity| world.get::<Trap>(**entity).is_some())
+        .copied()
+}
+
+pub fn trigger_trap_at(entity: Entity, coordinates: GridVector, world: &mut World) {
+    let Some(trap_entity) = get_trap_at(coordinates, world) else {
+        return;
+    };
+
+    let mut trap = world.get_mut::<Trap>(trap_entity).unwrap();
+    trap.hidden = false;
+    let template = trap.kind.template();
+    info!("{} triggers", template.name);
+
+    if let (Some(effect), Some(mut status_effects)) =
+        (template.on_hit, world.get_mut::<StatusEffects>(entity))
+    {
+        status_effects.add(effect);
+    }
+    if template.teleports {
+        item_effects::teleport(entity, world);
+    }
+    noise::emit_noise(coordinates, template.noise_radius, Some(entity), world);
+    if template.damage > 0 {
+        actions::apply_damage(entity, template.damage, world);
+    }
+}
+
+pub fn search_for_traps(entity: Entity, chance: f64, world: &mut World) {
+    let coordinates = world.get::<GridPosition>(entity).unwrap().coordinates;
+
+    for direction in GridDirection::ALL {
+        let Some(trap_entity) =
+            get_trap_at(coordinates + GridVector::from_direction(&direction), world)
+        else {
+            continue;
+        };
+
+        let mut trap = world.get_mut::<Trap>(trap_entity).unwrap();
+        if trap.hidden && thread_rng().gen_bool(chance) {
+            trap.hidden = false;
+            info!("you find a {}", trap.kind.template().name);
+        }
+    }
+}
+
+pub fn search_for_secret_doors(entity: Entity, chance: f64, world: &mut World) {
+    let coordinates = world.get::<GridPosition>(entity).unwrap().coordinates;
+
+    for direction in GridDirection::ALL {
+        let Some(door_entity) =
+            utils::get_door_at(coordinates + GridVector::from_direction(&direction), world)
+        else {
+            continue;
+        };
+
+        let mut door = world.get_mut::<Door>(door_entity).unwrap();
+        if door.secret && thread_rng().gen_bool(chance) {
+            door.reveal();
+            info!("you find a secret door");
+        }
+    }
+}
@@ -1,10 +1,46 @@
-use bevy::{prelude::*, utils::HashSet};
+use std::collections::VecDeque;
+
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use rand::prelude::*;
 
 use crate::game::components::*;
 use crate::game::directions::GridDirection;
-use crate::game::model::CharacterType;
+use crate::game::model::{Attitude, StatusEffectKind};
+use crate::game::resources::{
+    ExploredTiles, FactionTable, IdentifiedItems, ItemAppearances, LightMap, SpatialIndex,
+    VisibleTiles,
+};
+use crate::game::services::lighting;
+use crate::game::templates::{ItemKind, RangedStats, StatModifiers};
 use crate::game::vector::GridVector;
 
+const MAX_PATH_SEARCH: usize = 4096;
+const CONFUSED_STEP_CHANCE: f64 = 0.5;
+// for anything that fights without combat stats of its own
+const UNTRAINED_STATS: CombatStats = CombatStats {
+    attack: 1,
+    defense: 0,
+};
+
+pub fn confuse_direction(
+    entity: Entity,
+    direction: GridDirection,
+    world: &mut World,
+) -> GridDirection {
+    let confused = world
+        .get::<StatusEffects>(entity)
+        .is_some_and(|status_effects| status_effects.has(StatusEffectKind::Confusion));
+
+    if confused && thread_rng().gen_bool(CONFUSED_STEP_CHANCE) {
+        *GridDirection::ALL.choose(&mut thread_rng()).unwrap()
+    } else {
+        direction
+    }
+}
+
 pub fn get_shifted_coordinates(
     entity: Entity,
     direction: GridDirection,
@@ -23,44 +59,406 @@ pub fn get_solids(world: &mut World) -> HashSet<GridVector> {
         .collect::<HashSet<GridVector>>()
 }
 
-pub fn get_static_solids(world: &mut World) -> HashSet<GridVector> {
-    let mut query =
-        world.query_filtered::<&GridPosition, (With<Solid>, Without<Character>, Without<Door>)>();
-    query
-        .iter(world)
+pub fn is_solids_at(coordinates: GridVector, world: &mut World) -> bool {
+    world
+        .resource::<SpatialIndex>()
+        .entities_at(&coordinates)
+        .iter()
+        .any(|entity| world.get::<Solid>(*entity).is_some())
+}
+
+pub fn is_static_solid_at(coordinates: GridVector, world: &mut World) -> bool {
+    world
+        .resource::<SpatialIndex>()
+        .entities_at(&coordinates)
+        .iter()
+        .filter_map(|entity| world.get_entity(*entity))
+        .any(|entity| {
+            entity.contains::<Solid>()
+                && !entity.contains::<Character>()
+                && entity.get::<Door>().is_none_or(|door| door.secret)
+        })
+}
+
+pub fn is_opaque_at(coordinates: GridVector, world: &mut World) -> bool {
+    world
+        .resource::<SpatialIndex>()
+        .entities_at(&coordinates)
+        .iter()
+        .filter_map(|entity| world.get_entity(*entity))
+        .any(|entity| entity.contains::<Solid>() && !entity.contains::<Character>())
+}
+
+pub fn has_line_of_sight(from: GridVector, to: GridVector, world: &mut World) -> bool {
+    let line = from.line_to(&to);
+    line.iter()
+        .skip(1)
+        .take(line.len().saturating_sub(2))
+        .all(|coordinates| !is_opaque_at(*coordinates, world))
+}
+
+pub fn can_see(entity: Entity, coordinates: GridVector, world: &mut World) -> bool {
+    let Some(from) = world
+        .get::<GridPosition>(entity)
         .map(|position| position.coordinates)
-        .collect::<HashSet<GridVector>>()
+    else {
+        return false;
+    };
+
+    let radius = get_vision_radius(entity, world);
+    from.distance_max(&coordinates) <= radius
+        && has_line_of_sight(from, coordinates, world)
+        && lighting::is_lit_for(from, coordinates, world.resource::<LightMap>())
 }
 
-pub fn is_solids_at(coordinates: GridVector, world: &mut World) -> bool {
-    let mut query = world.query_filtered::<&GridPosition, With<Solid>>();
-    query
+pub fn get_vision_radius(entity: Entity, world: &mut World) -> i32 {
+    let blinded = world
+        .get::<StatusEffects>(entity)
+        .is_some_and(|status_effects| status_effects.has(StatusEffectKind::Blindness));
+    if blinded {
+        return 0;
+    }
+
+    let radius = world
+        .get::<Vision>(entity)
+        .map_or(0, |vision| vision.radius);
+    (radius + get_stat_modifiers(entity, world).vision).max(0)
+}
+
+pub fn get_stat_modifiers(entity: Entity, world: &mut World) -> StatModifiers {
+    let Some(equipment) = world.get::<Equipment>(entity) else {
+        return StatModifiers::default();
+    };
+
+    StatModifiers::from_items(
+        equipment
+            .items()
+            .filter_map(|item| world.get::<Item>(item))
+            .map(|item| item.kind),
+    )
+}
+
+pub fn get_attack(entity: Entity, world: &mut World) -> i32 {
+    let modifiers = get_stat_modifiers(entity, world);
+    world
+        .get::<CombatStats>(entity)
+        .unwrap_or(&UNTRAINED_STATS)
+        .attack_with(&modifiers, world.get::<Satiation>(entity))
+}
+
+pub fn get_defense(entity: Entity, world: &mut World) -> i32 {
+    let modifiers = get_stat_modifiers(entity, world);
+    world
+        .get::<CombatStats>(entity)
+        .unwrap_or(&UNTRAINED_STATS)
+        .defense_with(&modifiers)
+}
+
+pub fn get_visible_enemy(entity: Entity, world: &mut World) -> Option<(Entity, GridVector)> {
+    get_visible_enemies(entity, world).into_iter().next()
+}
+
+pub fn get_visible_enemies(entity: Entity, world: &mut World) -> Vec<(Entity, GridVector)> {
+    let Some(coordinates) = world
+        .get::<GridPosition>(entity)
+        .map(|position| position.coordinates)
+    else {
+        return Vec::new();
+    };
+
+    let mut query = world.query_filtered::<(Entity, &GridPosition), With<Character>>();
+    let mut characters = query
         .iter(world)
-        .filter(|position| position.coordinates == coordinates)
-        .next()
-        .is_some()
+        .filter(|(other, _)| *other != entity)
+        .map(|(other, position)| (other, position.coordinates))
+        .collect::<Vec<(Entity, GridVector)>>();
+    characters.sort_by_key(|(_, other_coordinates)| coordinates.distance_max(other_coordinates));
+
+    characters
+        .into_iter()
+        .filter(|(other, other_coordinates)| {
+            is_hostile(entity, *other, world) && can_see(entity, *other_coordinates, world)
+        })
+        .collect()
 }
 
-pub fn get_character_at(
-    character_type: CharacterType,
-    coordinates: GridVector,
+pub fn trace_projectile(
+    from: GridVector,
+    to: GridVector,
+    range: i32,
     world: &mut World,
-) -> Option<Entity> {
-    let mut query = world.query::<(Entity, &GridPosition, &Character)>();
-    query
-        .iter(world)
-        .filter(|(_, position, character)| {
-            position.coordinates == coordinates && character.character_type == character_type
+) -> (Vec<GridVector>, Option<Entity>) {
+    let mut path = Vec::new();
+
+    for coordinates in from.line_to(&to).into_iter().skip(1) {
+        if from.distance_max(&coordinates) > range || is_opaque_at(coordinates, world) {
+            break;
+        }
+
+        path.push(coordinates);
+
+        if let Some(character) = get_character_at(coordinates, world) {
+            return (path, Some(character));
+        }
+    }
+
+    (path, None)
+}
+
+// only walks tiles the player has explored, so a path never gives away unseen parts of the map
+pub fn find_path(from: GridVector, to: GridVector, world: &mut World) -> Option<Vec<GridVector>> {
+    let mut previous = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    previous.insert(from, from);
+    queue.push_back(from);
+
+    while let Some(coordinates) = queue.pop_front() {
+        if coordinates == to {
+            let mut path = vec![to];
+            let mut current = to;
+            while let Some(step) = previous.get(&current).copied().filter(|step| *step != from) {
+                path.push(step);
+                current = step;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        if previous.len() > MAX_PATH_SEARCH {
+            break;
+        }
+
+        for direction in GridDirection::ALL {
+            let next = coordinates + GridVector::from_direction(&direction);
+            if !previous.contains_key(&next)
+                && world.resource::<ExploredTiles>().contains(&next)
+                && !is_static_solid_at(next, world)
+            {
+                previous.insert(next, coordinates);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+pub fn get_reachable_floors(from: GridVector, world: &mut World) -> Vec<GridVector> {
+    let mut reachable = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    reachable.insert(from);
+    queue.push_back(from);
+
+    while let Some(coordinates) = queue.pop_front() {
+        for direction in GridDirection::ALL {
+            let next = coordinates + GridVector::from_direction(&direction);
+            if !reachable.contains(&next)
+                && is_floor_at(next, world)
+                && !is_static_solid_at(next, world)
+            {
+                reachable.insert(next);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    reachable.into_iter().collect()
+}
+
+pub fn is_floor_at(coordinates: GridVector, world: &mut World) -> bool {
+    world
+        .resource::<SpatialIndex>()
+        .entities_at(&coordinates)
+        .iter()
+        .any(|entity| world.get::<Floor>(*entity).is_some())
+}
+
+pub fn describe_tile(coordinates: GridVector, world: &mut World) -> String {
+    if !world.resource::<ExploredTiles>().contains(&coordinates) {
+        return "unexplored".to_string();
+    }
+    if !world.resource::<VisibleTiles>().contains(&coordinates) {
+        return "you can't see there".to_string();
+    }
+
+    let names = world
+        .resource::<SpatialIndex>()
+        .entities_at(&coordinates)
+        .iter()
+        .filter_map(|entity| world.get_entity(*entity))
+        .filter(|entity| !entity.get::<Trap>().is_some_and(|trap| trap.hidden))
+        .map(|entity| {
+            if entity.contains::<PlayerControlled>() {
+                "you".to_string()
+            } else if let Some(monster) = entity.get::<Monster>() {
+                monster.kind.template().name.to_string()
+            } else if let Some(item) = entity.get::<Item>() {
+                get_item_name(item.kind, world)
+            } else if let Some(trap) = entity.get::<Trap>() {
+                trap.kind.template().name.to_string()
+            } else if let Some(door) = entity.get::<Door>() {
+                if door.secret {
+                    "wall"
+                } else if door.closed {
+                    "closed door"
+                } else {
+                    "open door"
+                }
+                .to_string()
+            } else {
+                entity
+                    .get::<Name>()
+                    .map_or(String::new(), |name| name.to_string())
+            }
         })
-        .map(|(entity, _, _)| entity)
-        .next()
+        .collect::<Vec<String>>();
+
+    if names.is_empty() {
+        "nothing".to_string()
+    } else {
+        names.join(", ")
+    }
+}
+
+pub fn get_item_name(kind: ItemKind, world: &World) -> String {
+    world
+        .resource::<ItemAppearances>()
+        .name_of(kind, world.resource::<IdentifiedItems>())
+        .to_string()
+}
+
+pub fn get_ranged_stats(entity: Entity, world: &mut World) -> Option<RangedStats> {
+    if let Some(ranged_attack) = world.get::<RangedAttack>(entity) {
+        return Some(ranged_attack.0);
+    }
+
+    let weapon = world.get::<Equipment>(entity)?.weapon?;
+    world.get::<Item>(weapon)?.kind.template().ranged
+}
+
+pub fn take_from_inventory(entity: Entity, item: Entity, world: &mut World) -> bool {
+    let Some(mut inventory) = world.get_mut::<Inventory>(entity) else {
+        return false;
+    };
+    let Some(slot) = inventory.iter().position(|other| *other == item) else {
+        return false;
+    };
+    inventory.remove(slot);
+
+    if let Some(mut equipment) = world.get_mut::<Equipment>(entity) {
+        equipment.remove(item);
+    }
+
+    true
+}
+
+pub fn drop_inventory(entity: Entity, world: &mut World) {
+    let Some(coordinates) = world
+        .get::<GridPosition>(entity)
+        .map(|position| position.coordinates)
+    else {
+        return;
+    };
+
+    let items = world
+        .get_mut::<Inventory>(entity)
+        .map(|mut inventory| std::mem::take(&mut inventory.0))
+        .unwrap_or_default();
+    if let Some(mut equipment) = world.get_mut::<Equipment>(entity) {
+        *equipment = Equipment::default();
+    }
+
+    for item in items {
+        place_on_map(item, coordinates, world);
+    }
+}
+
+pub fn get_character_at(coordinates: GridVector, world: &mut World) -> Option<Entity> {
+    world
+        .resource::<SpatialIndex>()
+        .entities_at(&coordinates)
+        .iter()
+        .find(|entity| world.get::<Character>(**entity).is_some())
+        .copied()
+}
+
+pub fn get_attitude(entity: Entity, other: Entity, world: &mut World) -> Attitude {
+    if entity == other {
+        return Attitude::Friendly;
+    }
+
+    let holds_grudge = |entity: Entity, other: Entity| {
+        world
+            .get::<Grudges>(entity)
+            .is_some_and(|grudges| grudges.contains(&other))
+    };
+
+    if holds_grudge(entity, other) || holds_grudge(other, entity) {
+        return Attitude::Hostile;
+    }
+
+    match (world.get::<Faction>(entity), world.get::<Faction>(other)) {
+        (Some(faction), Some(other_faction)) => world
+            .resource::<FactionTable>()
+            .get_attitude(*faction, *other_faction),
+        _ => Attitude::Neutral,
+    }
+}
+
+pub fn is_hostile(entity: Entity, other: Entity, world: &mut World) -> bool {
+    get_attitude(entity, other, world) == Attitude::Hostile
+}
+
+pub fn get_item_at(coordinates: GridVector, world: &mut World) -> Option<Entity> {
+    world
+        .resource::<SpatialIndex>()
+        .entities_at(&coordinates)
+        .iter()
+        .find(|entity| world.get::<Item>(**entity).is_some())
+        .copied()
 }
 
 pub fn get_door_at(coordinates: GridVector, world: &mut World) -> Option<Entity> {
-    let mut query = world.query_filtered::<(Entity, &GridPosition), With<Door>>();
-    query
-        .iter(world)
-        .filter(|(_, position)| position.coordinates == coordinates)
-        .map(|(entity, _)| entity)
-        .next()
+    world
+        .resource::<SpatialIndex>()
+        .entities_at(&coordinates)
+        .iter()
+        .find(|entity| world.get::<Door>(**entity).is_some())
+        .copied()
+}
+
+pub fn set_coordinates(entity: Entity, coordinates: GridVector, world: &mut World) {
+    let Some(mut position) = world.get_mut::<GridPosition>(entity) else {
+        return;
+    };
+
+    position.coordinates = coordinates;
+    world
+        .resource_mut::<SpatialIndex>()
+        .insert(entity, coordinates);
+}
+
+pub fn remove_from_map(entity: Entity, world: &mut World) {
+    world.resource_mut::<SpatialIndex>().remove(entity);
+    world.entity_mut(entity).remove::<GridPosition>();
+}
+
+pub fn place_on_map(entity: Entity, coordinates: GridVector, world: &mut World) {
+    world.entity_mut(entity).insert(GridPosition {
+        coordinates,
+        direction: None,
+    });
+    world
+        .resource_mut::<SpatialIndex>()
+        .insert(entity, coordinates);
+}
+
+pub fn despawn(entity: Entity, world: &mut World) {
+    world.resource_mut::<SpatialIndex>().remove(entity);
+    // render backends may hang their own children off map entities
+    if let Some(entity) = world.get_entity_mut(entity) {
+        entity.despawn_recursive();
+    }
 }
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::game::components::*;
+use crate::game::resources::{ExploredTiles, VisibleTiles};
+use crate::game::services::utils;
+use crate::game::vector::GridVector;
+
+pub fn get_visible_tiles(entity: Entity, world: &mut World) -> HashSet<GridVector> {
+    let Some(origin) = world
+        .get::<GridPosition>(entity)
+        .map(|position| position.coordinates)
+    else {
+        return HashSet::new();
+    };
+
+    let radius = utils::get_vision_radius(entity, world);
+    let mut visible_tiles = HashSet::new();
+    for y in -radius..=radius {
+        for x in -radius..=radius {
+            let coordinates = origin + GridVector::new(x, y);
+            if utils::has_line_of_sight(origin, coordinates, world) {
+                visible_tiles.insert(coordinates);
+            }
+        }
+    }
+
+    visible_tiles
+}
+
+pub fn update_player_view(world: &mut World) {
+    let mut query = world.query_filtered::<Entity, With<PlayerControlled>>();
+    let Ok(player) = query.get_single(world) else {
+        return;
+    };
+
+    let visible_tiles = get_visible_tiles(player, world);
+    world
+        .resource_mut::<ExploredTiles>()
+        .extend(visible_tiles.iter().copied());
+    world.resource_mut::<VisibleTiles>().0 = visible_tiles;
+}
+
+pub fn reveal_map(world: &mut World) {
+    let mut query = world
+        .query_filtered::<&GridPosition, (With<MapEntity>, Without<Character>, Without<Item>)>();
+    let tiles = query
+        .iter(world)
+        .map(|position| position.coordinates)
+        .collect::<Vec<GridVector>>();
+
+    world.resource_mut::<ExploredTiles>().extend(tiles);
+}
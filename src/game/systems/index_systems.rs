@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+
+use crate::game::components::*;
+use crate::game::resources::*;
+
+pub fn update_spatial_index(
+    mut spatial_index: ResMut<SpatialIndex>,
+    position_query: Query<(Entity, &GridPosition), Changed<GridPosition>>,
+    mut removed_positions: RemovedComponents<GridPosition>,
+) {
+    for entity in removed_positions.read() {
+        spatial_index.remove(entity);
+    }
+
+    for (entity, position) in &position_query {
+        spatial_index.insert(entity, position.coordinates);
+    }
+}
@@ -1,23 +1,34 @@
 use bevy::prelude::*;
 
-use crate::game::resources::AcceptInput;
+use crate::game::resources::{AcceptInput, GamePaused};
+use crate::game::systems::index_systems::*;
 use crate::game::systems::spawn_systems::*;
 use crate::game::systems::turn_systems::*;
+use crate::game::systems::view_systems::*;
 use crate::states::MainState;
 
+mod index_systems;
 mod spawn_systems;
 mod turn_systems;
+mod view_systems;
 
 pub struct GameSystemsPlugin;
 
 impl Plugin for GameSystemsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(MainState::Game), spawn_level_2)
+        app.add_systems(Startup, log_game_seed)
+            .add_systems(OnEnter(MainState::Game), spawn_level_2)
             .add_systems(
                 Update,
                 (
-                    process_turn.run_if(resource_exists_and_equals(AcceptInput(true))),
-                    update_input_cooldown.run_if(resource_exists_and_equals(AcceptInput(false))),
+                    update_spatial_index.before(process_turn),
+                    process_turn
+                        .run_if(resource_exists_and_equals(AcceptInput(true)))
+                        .run_if(resource_exists_and_equals(GamePaused(false))),
+                    update_player_view.after(process_turn),
+                    update_accept_input
+                        .before(process_turn)
+                        .run_if(resource_exists_and_equals(AcceptInput(false))),
                 )
                     .run_if(in_state(MainState::Game)),
             );
@@ -1,7 +1,9 @@
 use crate::game::services;
 use bevy::prelude::*;
 
+use crate::game::resources::GameSeed;
 use crate::game::services::game_world::GameWorld;
+use crate::game::templates::MonsterKind;
 use crate::game::vector::GridVector;
 
 pub fn spawn_level(world: &mut World) {
@@ -19,47 +21,18 @@ pub fn spawn_level(world: &mut World) {
     }
 
     game_world.add_player(GridVector::new(0, 0));
-    game_world.add_monster(GridVector::new(5, 5));
-    game_world.add_monster(GridVector::new(5, -5));
-    game_world.add_monster(GridVector::new(-5, 5));
-    game_world.add_monster(GridVector::new(-5, -5));
+    game_world.add_monster(GridVector::new(5, 5), MonsterKind::Goblin);
+    game_world.add_monster(GridVector::new(5, -5), MonsterKind::Goblin);
+    game_world.add_monster(GridVector::new(-5, 5), MonsterKind::Goblin);
+    game_world.add_monster(GridVector::new(-5, -5), MonsterKind::Goblin);
 
     game_world.spawn_world(world);
 }
 
 pub fn spawn_level_2(world: &mut World) {
-    info!("create_level");
-
-    let mut rng = rand::thread_rng();
-
-    let rooms_horizontal = 9;
-    let rooms_vertical = 9;
-
-    let mut game_world = GameWorld::empty();
-
-    let settings = services::dungeon_generator::DungeonSettings {
-        anchor: GridVector::zero(),
-        rooms_horizontal: rooms_horizontal,
-        rooms_vertical: rooms_vertical,
-        rooms_width: 3,
-        rooms_height: 3,
-        spawn_wall_probability: 0.25,
-        spawn_passege_probability: 0.25,
-        spawn_door_probability: 0.25,
-        fill_room_probability: 0.5,
-        always_fill_outer_borders: true,
-        always_fill_lone_columns: false,
-        always_fill_closed_rooms: true,
-    };
-
-    services::dungeon_generator::create_dungeon(&mut game_world, settings, &mut rng);
-
-    game_world.add_player(GridVector::new(
-        4 * (rooms_horizontal as i32 / 2) + 2,
-        4 * (rooms_vertical as i32 / 2) + 2,
-    ));
-
-    game_world.add_monster(GridVector::new(4 * 0 + 2, 4 * 0 + 2));
+    services::levels::create_level(world);
+}
 
-    game_world.spawn_world(world);
+pub fn log_game_seed(seed: Res<GameSeed>) {
+    info!("game seed: {}", seed.0);
 }
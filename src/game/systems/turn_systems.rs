@@ -5,12 +5,11 @@ use crate::game::components::*;
 use crate::game::resources::*;
 use crate::game::services;
 
-pub fn update_input_cooldown(
+pub fn update_accept_input(
     mut accept_input: ResMut<AcceptInput>,
-    mut input_cooldown: ResMut<InputCooldown>,
-    time: Res<Time>,
+    animation_lock: Res<AnimationLock>,
 ) {
-    if input_cooldown.tick(time.delta()).finished() {
+    if !animation_lock.0 {
         accept_input.0 = true;
     }
 }
@@ -27,6 +26,12 @@ pub fn process_turn(world: &mut World) {
             break;
         };
 
+        // the selection may have died since it was made
+        if world.get_entity(entity).is_none() {
+            selected_entity(world).0 = None;
+            continue;
+        }
+
         if !processed_entities.insert(entity) {
             break;
         }
@@ -40,7 +45,7 @@ pub fn process_turn(world: &mut World) {
         if is_player {
             player_acted = services::behaviors::player_behavior(entity, world);
             if player_acted {
-                reset_input_cooldown(world);
+                block_input(world);
                 selected_entity(world).0 = None;
             }
         } else {
@@ -58,8 +63,7 @@ fn selected_entity(world: &mut World) -> Mut<SelectedEntity> {
     world.resource_mut::<SelectedEntity>()
 }
 
-fn reset_input_cooldown(world: &mut World) {
-    world.resource_mut::<InputCooldown>().reset();
+fn block_input(world: &mut World) {
     world.resource_mut::<AcceptInput>().0 = false;
 }
 
@@ -80,6 +84,16 @@ fn select_next_entity(world: &mut World) -> Option<Entity> {
         for mut cooldown in query.iter_mut(world) {
             cooldown.0 -= min_cooldown;
         }
+
+        world.resource_mut::<GameTime>().0 += min_cooldown;
+        services::status_effects::tick_status_effects(min_cooldown, world);
+        services::status_effects::tick_natural_regeneration(min_cooldown, world);
+        services::hunger::tick_hunger(min_cooldown, world);
+
+        // poison or hunger may have killed the entity whose turn it is
+        if next_entity.is_some_and(|entity| world.get_entity(entity).is_none()) {
+            return select_next_entity(world);
+        }
     }
 
     selected_entity(world).0 = next_entity;
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use crate::game::components::*;
+use crate::game::resources::*;
+use crate::game::services;
+use crate::game::vector::GridVector;
+
+pub fn update_player_view(world: &mut World, mut last_update: Local<Option<(GridVector, f32)>>) {
+    let mut query = world.query_filtered::<&GridPosition, With<PlayerControlled>>();
+    let Ok(coordinates) = query.get_single(world).map(|position| position.coordinates) else {
+        return;
+    };
+
+    // the view and lighting only change when the player moves or time passes
+    let current = (coordinates, world.resource::<GameTime>().0);
+    if *last_update == Some(current) {
+        return;
+    }
+    *last_update = Some(current);
+
+    services::lighting::update_light_map(world);
+    services::visibility::update_player_view(world);
+}
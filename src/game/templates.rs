@@ -0,0 +1,551 @@
+use bevy::prelude::*;
+
+use crate::game::components::{Door, Faction, LightSource};
+use crate::game::model::{AiKind, StatusEffect, StatusEffectKind};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum MonsterKind {
+    Rat,
+    Goblin,
+    Orc,
+    Skeleton,
+    Troll,
+    Dog,
+}
+
+pub struct MonsterTemplate {
+    pub name: &'static str,
+    pub sprite_index: usize,
+    pub faction: Faction,
+    pub max_health: i32,
+    pub attack: i32,
+    pub defense: i32,
+    pub tame_chance: f32,
+    pub ai_kind: AiKind,
+    pub sight_radius: i32,
+    pub ranged: Option<RangedStats>,
+    pub on_hit: Option<StatusEffect>,
+    pub equipment: &'static [ItemKind],
+    pub experience: u32,
+    pub light: Option<LightSource>,
+}
+
+impl MonsterKind {
+    pub fn template(&self) -> MonsterTemplate {
+        match self {
+            Self::Rat => MonsterTemplate {
+                name: "rat",
+                sprite_index: 9,
+                faction: Faction::Vermin,
+                max_health: 3,
+                attack: 2,
+                defense: 0,
+                tame_chance: 0.0,
+                ai_kind: AiKind::Aggressive,
+                sight_radius: 5,
+                ranged: None,
+                on_hit: Some(StatusEffect::new(StatusEffectKind::Poison, 3.0, 1)),
+                equipment: &[],
+                experience: 1,
+                light: None,
+            },
+            Self::Goblin => MonsterTemplate {
+                name: "goblin",
+                sprite_index: 10,
+                faction: Faction::Greenskins,
+                max_health: 6,
+                attack: 3,
+                defense: 0,
+                tame_chance: 0.0,
+                ai_kind: AiKind::Cautious,
+                sight_radius: 6,
+                ranged: Some(RangedStats {
+                    range: 5,
+                    damage: 2,
+                }),
+                on_hit: None,
+                equipment: &[ItemKind::Dagger],
+                experience: 3,
+                light: None,
+            },
+            Self::Orc => MonsterTemplate {
+                name: "orc",
+                sprite_index: 11,
+                faction: Faction::Greenskins,
+                max_health: 10,
+                attack: 2,
+                defense: 1,
+                tame_chance: 0.0,
+                ai_kind: AiKind::Aggressive,
+                sight_radius: 6,
+                ranged: None,
+                on_hit: None,
+                equipment: &[ItemKind::Sword, ItemKind::LeatherArmour],
+                experience: 6,
+                light: None,
+            },
+            Self::Skeleton => MonsterTemplate {
+                name: "skeleton",
+                sprite_index: 12,
+                faction: Faction::Undead,
+                max_health: 8,
+                attack: 4,
+                defense: 2,
+                tame_chance: 0.0,
+                ai_kind: AiKind::Sleeper,
+                sight_radius: 5,
+                ranged: None,
+                on_hit: Some(StatusEffect::new(StatusEffectKind::Slow, 5.0, 1)),
+                equipment: &[],
+                experience: 6,
+                light: Some(LightSource::new(2, Color::rgb(0.5, 0.6, 1.0))),
+            },
+            Self::Troll => MonsterTemplate {
+                name: "troll",
+                sprite_index: 13,
+                faction: Faction::Greenskins,
+                max_health: 20,
+                attack: 7,
+                defense: 2,
+                tame_chance: 0.0,
+                ai_kind: AiKind::Sleeper,
+                sight_radius: 4,
+                ranged: None,
+                on_hit: Some(StatusEffect::new(StatusEffectKind::Confusion, 3.0, 1)),
+                equipment: &[],
+                experience: 15,
+                light: None,
+            },
+            Self::Dog => MonsterTemplate {
+                name: "dog",
+                sprite_index: 14,
+                faction: Faction::Wildlife,
+                max_health: 6,
+                attack: 3,
+                defense: 0,
+                tame_chance: 0.5,
+                ai_kind: AiKind::Cautious,
+                sight_radius: 7,
+                ranged: None,
+                on_hit: None,
+                equipment: &[],
+                experience: 2,
+                light: None,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ItemKind {
+    Dagger,
+    Sword,
+    Bow,
+    LeatherArmour,
+    HealingPotion,
+    HastePotion,
+    RegenerationPotion,
+    BlindnessPotion,
+    TeleportScroll,
+    MagicMappingScroll,
+    FearScroll,
+    RingOfSpeed,
+    RingOfSight,
+    Ration,
+}
+
+impl ItemKind {
+    pub const ALL: [Self; 14] = [
+        Self::Dagger,
+        Self::Sword,
+        Self::Bow,
+        Self::LeatherArmour,
+        Self::HealingPotion,
+        Self::HastePotion,
+        Self::RegenerationPotion,
+        Self::BlindnessPotion,
+        Self::TeleportScroll,
+        Self::MagicMappingScroll,
+        Self::FearScroll,
+        Self::RingOfSpeed,
+        Self::RingOfSight,
+        Self::Ration,
+    ];
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ItemClass {
+    #[default]
+    Misc,
+    Weapon,
+    Armour,
+    Potion,
+    Scroll,
+    Ring,
+    Food,
+}
+
+impl ItemClass {
+    pub fn slot(&self) -> Option<EquipSlot> {
+        match self {
+            Self::Weapon => Some(EquipSlot::Weapon),
+            Self::Armour => Some(EquipSlot::Armour),
+            Self::Ring => Some(EquipSlot::Ring),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum EquipSlot {
+    Weapon,
+    Armour,
+    Ring,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StatModifiers {
+    pub attack: i32,
+    pub defense: i32,
+    // fraction shaved off every cooldown
+    pub speed: f32,
+    pub vision: i32,
+}
+
+impl StatModifiers {
+    pub fn combine(&self, other: &Self) -> Self {
+        Self {
+            attack: self.attack + other.attack,
+            defense: self.defense + other.defense,
+            speed: self.speed + other.speed,
+            vision: self.vision + other.vision,
+        }
+    }
+
+    pub fn from_items(kinds: impl Iterator<Item = ItemKind>) -> Self {
+        kinds.fold(Self::default(), |modifiers, kind| {
+            modifiers.combine(&kind.template().modifiers)
+        })
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        (1.0 - self.speed).max(0.25)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum UseEffect {
+    Heal(i32),
+    Feed(f32),
+    Teleport,
+    MagicMapping,
+    Fear { radius: i32, duration: f32 },
+    Status(StatusEffect),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RangedStats {
+    pub range: i32,
+    pub damage: i32,
+}
+
+#[derive(Default)]
+pub struct ItemTemplate {
+    pub name: &'static str,
+    pub sprite_index: usize,
+    pub class: ItemClass,
+    pub ranged: Option<RangedStats>,
+    pub throw_damage: i32,
+    pub use_effect: Option<UseEffect>,
+    pub modifiers: StatModifiers,
+}
+
+impl ItemKind {
+    pub fn template(&self) -> ItemTemplate {
+        match self {
+            Self::Dagger => ItemTemplate {
+                name: "dagger",
+                sprite_index: 16,
+                class: ItemClass::Weapon,
+                throw_damage: 3,
+                modifiers: StatModifiers {
+                    attack: 1,
+                    ..default()
+                },
+                ..default()
+            },
+            Self::Sword => ItemTemplate {
+                name: "sword",
+                sprite_index: 17,
+                class: ItemClass::Weapon,
+                throw_damage: 1,
+                modifiers: StatModifiers {
+                    attack: 3,
+                    ..default()
+                },
+                ..default()
+            },
+            Self::Bow => ItemTemplate {
+                name: "bow",
+                sprite_index: 21,
+                class: ItemClass::Weapon,
+                ranged: Some(RangedStats {
+                    range: 8,
+                    damage: 4,
+                }),
+                throw_damage: 1,
+                ..default()
+            },
+            Self::LeatherArmour => ItemTemplate {
+                name: "leather armour",
+                sprite_index: 18,
+                class: ItemClass::Armour,
+                modifiers: StatModifiers {
+                    defense: 2,
+                    speed: -0.1,
+                    ..default()
+                },
+                ..default()
+            },
+            Self::HealingPotion => ItemTemplate {
+                name: "potion of healing",
+                sprite_index: 19,
+                class: ItemClass::Potion,
+                throw_damage: 1,
+                use_effect: Some(UseEffect::Heal(10)),
+                ..default()
+            },
+            Self::HastePotion => ItemTemplate {
+                name: "potion of haste",
+                sprite_index: 19,
+                class: ItemClass::Potion,
+                throw_damage: 1,
+                use_effect: Some(UseEffect::Status(StatusEffect::new(
+                    StatusEffectKind::Haste,
+                    20.0,
+                    1,
+                ))),
+                ..default()
+            },
+            Self::RegenerationPotion => ItemTemplate {
+                name: "potion of regeneration",
+                sprite_index: 19,
+                class: ItemClass::Potion,
+                throw_damage: 1,
+                use_effect: Some(UseEffect::Status(StatusEffect::new(
+                    StatusEffectKind::Regeneration,
+                    15.0,
+                    1,
+                ))),
+                ..default()
+            },
+            Self::BlindnessPotion => ItemTemplate {
+                name: "potion of blindness",
+                sprite_index: 19,
+                class: ItemClass::Potion,
+                throw_damage: 1,
+                use_effect: Some(UseEffect::Status(StatusEffect::new(
+                    StatusEffectKind::Blindness,
+                    10.0,
+                    1,
+                ))),
+                ..default()
+            },
+            Self::TeleportScroll => ItemTemplate {
+                name: "scroll of teleport",
+                sprite_index: 20,
+                class: ItemClass::Scroll,
+                use_effect: Some(UseEffect::Teleport),
+                ..default()
+            },
+            Self::MagicMappingScroll => ItemTemplate {
+                name: "scroll of magic mapping",
+                sprite_index: 20,
+                class: ItemClass::Scroll,
+                use_effect: Some(UseEffect::MagicMapping),
+                ..default()
+            },
+            Self::FearScroll => ItemTemplate {
+                name: "scroll of fear",
+                sprite_index: 20,
+                class: ItemClass::Scroll,
+                use_effect: Some(UseEffect::Fear {
+                    radius: 6,
+                    duration: 10.0,
+                }),
+                ..default()
+            },
+            Self::RingOfSpeed => ItemTemplate {
+                name: "ring of speed",
+                sprite_index: 22,
+                class: ItemClass::Ring,
+                modifiers: StatModifiers {
+                    speed: 0.2,
+                    ..default()
+                },
+                ..default()
+            },
+            Self::Ration => ItemTemplate {
+                name: "food ration",
+                sprite_index: 23,
+                class: ItemClass::Food,
+                use_effect: Some(UseEffect::Feed(800.0)),
+                ..default()
+            },
+            Self::RingOfSight => ItemTemplate {
+                name: "ring of sight",
+                sprite_index: 22,
+                class: ItemClass::Ring,
+                modifiers: StatModifiers {
+                    vision: 3,
+                    ..default()
+                },
+                ..default()
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum TrapKind {
+    Pit,
+    Dart,
+    Alarm,
+    Teleport,
+}
+
+pub struct TrapTemplate {
+    pub name: &'static str,
+    pub sprite_index: usize,
+    pub damage: i32,
+    pub on_hit: Option<StatusEffect>,
+    pub noise_radius: i32,
+    pub teleports: bool,
+    pub disarm_chance: f32,
+}
+
+impl TrapKind {
+    pub fn template(&self) -> TrapTemplate {
+        match self {
+            Self::Pit => TrapTemplate {
+                name: "pit trap",
+                sprite_index: 24,
+                damage: 3,
+                on_hit: None,
+                noise_radius: 0,
+                teleports: false,
+                disarm_chance: 0.8,
+            },
+            Self::Dart => TrapTemplate {
+                name: "dart trap",
+                sprite_index: 25,
+                damage: 2,
+                on_hit: Some(StatusEffect::new(StatusEffectKind::Poison, 5.0, 1)),
+                noise_radius: 0,
+                teleports: false,
+                disarm_chance: 0.6,
+            },
+            Self::Alarm => TrapTemplate {
+                name: "alarm trap",
+                sprite_index: 26,
+                damage: 0,
+                on_hit: None,
+                noise_radius: 15,
+                teleports: false,
+                disarm_chance: 0.7,
+            },
+            Self::Teleport => TrapTemplate {
+                name: "teleport trap",
+                sprite_index: 27,
+                damage: 0,
+                on_hit: None,
+                noise_radius: 0,
+                teleports: true,
+                disarm_chance: 0.5,
+            },
+        }
+    }
+}
+
+// secret doors pass for walls until they are found
+pub fn door_sprite_index(door: &Door) -> usize {
+    if door.secret {
+        1
+    } else if door.closed {
+        3
+    } else {
+        2
+    }
+}
+
+/// One row of a spawn table: `cost` is paid from the level budget, `weight` is relative
+/// to the other rows that are still affordable at the current depth.
+#[derive(Clone, Copy, Debug)]
+pub struct SpawnEntry<T> {
+    pub kind: T,
+    pub weight: f32,
+    pub cost: u32,
+    pub min_depth: u32,
+}
+
+impl<T> SpawnEntry<T> {
+    const fn new(kind: T, weight: f32, cost: u32, min_depth: u32) -> Self {
+        Self {
+            kind,
+            weight,
+            cost,
+            min_depth,
+        }
+    }
+}
+
+pub const MONSTER_SPAWN_TABLE: [SpawnEntry<MonsterKind>; 6] = [
+    SpawnEntry::new(MonsterKind::Rat, 10.0, 1, 1),
+    SpawnEntry::new(MonsterKind::Dog, 3.0, 1, 1),
+    SpawnEntry::new(MonsterKind::Goblin, 8.0, 2, 1),
+    SpawnEntry::new(MonsterKind::Orc, 5.0, 4, 2),
+    SpawnEntry::new(MonsterKind::Skeleton, 4.0, 4, 3),
+    SpawnEntry::new(MonsterKind::Troll, 2.0, 8, 5),
+];
+
+pub const ITEM_SPAWN_TABLE: [SpawnEntry<ItemKind>; 14] = [
+    SpawnEntry::new(ItemKind::Dagger, 4.0, 1, 1),
+    SpawnEntry::new(ItemKind::Sword, 2.0, 3, 2),
+    SpawnEntry::new(ItemKind::Bow, 2.0, 2, 1),
+    SpawnEntry::new(ItemKind::LeatherArmour, 3.0, 2, 1),
+    SpawnEntry::new(ItemKind::HealingPotion, 8.0, 1, 1),
+    SpawnEntry::new(ItemKind::Ration, 6.0, 1, 1),
+    SpawnEntry::new(ItemKind::HastePotion, 3.0, 2, 1),
+    SpawnEntry::new(ItemKind::RegenerationPotion, 3.0, 2, 1),
+    SpawnEntry::new(ItemKind::BlindnessPotion, 2.0, 1, 2),
+    SpawnEntry::new(ItemKind::TeleportScroll, 3.0, 2, 1),
+    SpawnEntry::new(ItemKind::MagicMappingScroll, 2.0, 2, 1),
+    SpawnEntry::new(ItemKind::FearScroll, 2.0, 2, 2),
+    SpawnEntry::new(ItemKind::RingOfSpeed, 1.0, 4, 3),
+    SpawnEntry::new(ItemKind::RingOfSight, 1.0, 3, 2),
+];
+
+pub const TRAP_SPAWN_TABLE: [SpawnEntry<TrapKind>; 4] = [
+    SpawnEntry::new(TrapKind::Pit, 4.0, 1, 1),
+    SpawnEntry::new(TrapKind::Dart, 4.0, 1, 1),
+    SpawnEntry::new(TrapKind::Alarm, 2.0, 1, 2),
+    SpawnEntry::new(TrapKind::Teleport, 1.0, 2, 3),
+];
+
+pub const POTION_APPEARANCES: [&str; 6] = [
+    "red potion",
+    "murky potion",
+    "fizzy potion",
+    "golden potion",
+    "bubbling potion",
+    "violet potion",
+];
+
+pub const SCROLL_APPEARANCES: [&str; 6] = [
+    "scroll labeled ZELGO MER",
+    "scroll labeled FOOBIE BLETCH",
+    "scroll labeled XIXAXA",
+    "scroll labeled ELBIB YLOH",
+    "scroll labeled VERR YED",
+    "scroll labeled PRATYAVAYAH",
+];
@@ -44,11 +44,37 @@ impl GridVector {
         }
     }
 
-    pub fn vec_x(&self) -> f32 {
-        (self.x as f32) * GRID_SIZE
-    }
-    pub fn vec_y(&self) -> f32 {
-        (self.y as f32) * GRID_SIZE
+    pub fn direction_to(&self, rhs: &Self) -> Option<GridDirection> {
+        let step = Self::new((rhs.x - self.x).signum(), (rhs.y - self.y).signum());
+        GridDirection::ALL
+            .into_iter()
+            .find(|direction| Self::from_direction(direction) == step)
+    }
+
+    pub fn line_to(&self, rhs: &Self) -> Vec<Self> {
+        let dx = (rhs.x - self.x).abs();
+        let dy = -(rhs.y - self.y).abs();
+        let step_x = (rhs.x - self.x).signum();
+        let step_y = (rhs.y - self.y).signum();
+        let mut error = dx + dy;
+
+        let mut current = *self;
+        let mut line = vec![current];
+
+        while current != *rhs {
+            let doubled_error = 2 * error;
+            if doubled_error >= dy {
+                error += dy;
+                current.x += step_x;
+            }
+            if doubled_error <= dx {
+                error += dx;
+                current.y += step_y;
+            }
+            line.push(current);
+        }
+
+        line
     }
 
     pub fn distance_max(&self, rhs: &Self) -> i32 {
@@ -0,0 +1,158 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+use crate::constants::*;
+use crate::game::components::*;
+use crate::game::events::CombatEvent;
+use crate::game::resources::{AcceptInput, AnimationLock, LightMap, VisibleTiles};
+use crate::graphics::components::{BumpTween, DamageFlash, MoveTween};
+use crate::graphics::systems::shaded_color;
+
+const MOVE_DURATION: f32 = 0.1;
+const BUMP_DURATION: f32 = 0.12;
+const BUMP_DISTANCE: f32 = 0.3;
+const FLASH_DURATION: f32 = 0.2;
+// turns without anything to animate still get a short beat
+const MIN_TURN_DURATION: f32 = 0.08;
+// longer jumps (teleports, stairs) snap instead of sliding across the map
+const MAX_TWEEN_DISTANCE: f32 = 2.0 * GRID_SIZE;
+
+pub fn start_move_tweens(
+    mut commands: Commands,
+    mut entity_query: Query<
+        (Entity, &mut Transform, &GridPosition, &Renderable),
+        Changed<GridPosition>,
+    >,
+) {
+    for (entity, mut transform, position, renderable) in &mut entity_query {
+        let to = position.coordinates.to_vec3(renderable.layer);
+        let from = transform.translation;
+        if from.distance(to) > MAX_TWEEN_DISTANCE {
+            transform.translation = to;
+            continue;
+        }
+
+        commands.entity(entity).insert(MoveTween {
+            from,
+            to,
+            timer: Timer::from_seconds(MOVE_DURATION, TimerMode::Once),
+        });
+    }
+}
+
+pub fn start_combat_animations(
+    mut commands: Commands,
+    mut combat_events: EventReader<CombatEvent>,
+    entity_query: Query<(&GridPosition, &Renderable), With<TextureAtlasSprite>>,
+) {
+    for event in combat_events.read() {
+        match *event {
+            CombatEvent::Attack { attacker, target } => {
+                let Ok((position, renderable)) = entity_query.get(attacker) else {
+                    continue;
+                };
+                let origin = position.coordinates.to_vec3(renderable.layer);
+                let direction = (target.to_vec3(renderable.layer) - origin).normalize_or_zero();
+                commands.entity(attacker).insert(BumpTween {
+                    origin,
+                    direction,
+                    timer: Timer::from_seconds(BUMP_DURATION, TimerMode::Once),
+                });
+            }
+            CombatEvent::Damage { target, .. } => {
+                if entity_query.get(target).is_ok() {
+                    commands.entity(target).insert(DamageFlash {
+                        timer: Timer::from_seconds(FLASH_DURATION, TimerMode::Once),
+                    });
+                }
+            }
+            CombatEvent::Heal { .. } | CombatEvent::Miss { .. } => {}
+        }
+    }
+}
+
+pub fn animate_moves(
+    mut commands: Commands,
+    mut tween_query: Query<(Entity, &mut Transform, &mut MoveTween)>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, mut tween) in &mut tween_query {
+        tween.timer.tick(time.delta());
+        transform.translation = tween.from.lerp(tween.to, tween.timer.percent());
+
+        if tween.timer.finished() {
+            commands.entity(entity).remove::<MoveTween>();
+        }
+    }
+}
+
+pub fn animate_bumps(
+    mut commands: Commands,
+    mut tween_query: Query<(Entity, &mut Transform, &mut BumpTween), Without<MoveTween>>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, mut tween) in &mut tween_query {
+        tween.timer.tick(time.delta());
+        let offset = (tween.timer.percent() * PI).sin() * BUMP_DISTANCE * GRID_SIZE;
+        transform.translation = tween.origin + tween.direction * offset;
+
+        if tween.timer.finished() {
+            transform.translation = tween.origin;
+            commands.entity(entity).remove::<BumpTween>();
+        }
+    }
+}
+
+pub fn animate_damage_flashes(
+    mut commands: Commands,
+    mut flash_query: Query<(
+        Entity,
+        &mut TextureAtlasSprite,
+        &GridPosition,
+        &Renderable,
+        &mut DamageFlash,
+    )>,
+    visible_tiles: Res<VisibleTiles>,
+    light_map: Res<LightMap>,
+    time: Res<Time>,
+) {
+    for (entity, mut sprite, position, renderable, mut flash) in &mut flash_query {
+        // fades back to the colour the fog of war gave the sprite
+        let color = shaded_color(
+            renderable.tint,
+            position.coordinates,
+            &visible_tiles,
+            &light_map,
+        );
+        flash.timer.tick(time.delta());
+        sprite.color = Color::RED * (1.0 - flash.timer.percent()) + color * flash.timer.percent();
+
+        if flash.timer.finished() {
+            sprite.color = color;
+            commands.entity(entity).remove::<DamageFlash>();
+        }
+    }
+}
+
+type TweeningQuery<'w, 's> = Query<'w, 's, (), Or<(With<MoveTween>, With<BumpTween>)>>;
+
+// holds the game's input until the last turn has finished playing out
+pub fn update_animation_lock(
+    mut animation_lock: ResMut<AnimationLock>,
+    accept_input: Res<AcceptInput>,
+    animation_query: TweeningQuery,
+    moved_query: Query<(), (Changed<GridPosition>, With<TextureAtlasSprite>)>,
+    mut turn_timer: Local<Option<Timer>>,
+    time: Res<Time>,
+) {
+    if accept_input.is_changed() && !accept_input.0 {
+        *turn_timer = Some(Timer::from_seconds(MIN_TURN_DURATION, TimerMode::Once));
+    }
+
+    let turn_running = turn_timer
+        .as_mut()
+        .is_some_and(|timer| !timer.tick(time.delta()).finished());
+
+    animation_lock.0 = turn_running || !animation_query.is_empty() || !moved_query.is_empty();
+}
@@ -0,0 +1,174 @@
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+use crate::constants::*;
+use crate::game::components::*;
+use crate::game::events::CombatEvent;
+use crate::game::resources::VisibleTiles;
+use crate::game::vector::GridVector;
+use crate::graphics::components::{FloatingText, HealthBar, HealthBarFill};
+
+const HEALTH_BAR_WIDTH: f32 = 12.0;
+const HEALTH_BAR_HEIGHT: f32 = 2.0;
+const FLOATING_TEXT_DURATION: f32 = 0.8;
+// in tiles over the whole duration
+const FLOATING_TEXT_RISE: f32 = 1.0;
+
+pub fn spawn_floating_text(
+    mut commands: Commands,
+    mut combat_events: EventReader<CombatEvent>,
+    visible_tiles: Res<VisibleTiles>,
+) {
+    for event in combat_events.read() {
+        let (coordinates, value, color) = match *event {
+            CombatEvent::Damage {
+                coordinates,
+                amount,
+                ..
+            } => (coordinates, format!("-{}", amount), Color::RED),
+            CombatEvent::Heal {
+                coordinates,
+                amount,
+                ..
+            } => (coordinates, format!("+{}", amount), Color::GREEN),
+            CombatEvent::Miss { coordinates } => {
+                (Some(coordinates), "miss".to_string(), Color::GRAY)
+            }
+            CombatEvent::Attack { .. } => continue,
+        };
+        let Some(coordinates) = coordinates.filter(|vec| visible_tiles.contains(vec)) else {
+            continue;
+        };
+
+        commands.spawn((
+            Name::new("floating text"),
+            FloatingText {
+                timer: Timer::from_seconds(FLOATING_TEXT_DURATION, TimerMode::Once),
+            },
+            Text2dBundle {
+                text: Text::from_section(
+                    value,
+                    TextStyle {
+                        font_size: 8.0,
+                        color,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_translation(floating_text_origin(coordinates))
+                    .with_scale(Vec3::splat(SCALE)),
+                ..default()
+            },
+        ));
+    }
+}
+
+fn floating_text_origin(coordinates: GridVector) -> Vec3 {
+    coordinates.to_vec3(70.0) + Vec3::new(0.0, GRID_SIZE * 0.5, 0.0)
+}
+
+pub fn animate_floating_text(
+    mut commands: Commands,
+    mut text_query: Query<(Entity, &mut Transform, &mut Text, &mut FloatingText)>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, mut text, mut floating_text) in &mut text_query {
+        floating_text.timer.tick(time.delta());
+        if floating_text.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let progress = floating_text.timer.percent();
+        transform.translation.y +=
+            FLOATING_TEXT_RISE * GRID_SIZE * time.delta_seconds() / FLOATING_TEXT_DURATION;
+        text.sections[0]
+            .style
+            .color
+            .set_a(1.0 - progress * progress);
+    }
+}
+
+// every monster gets a hidden bar up front, so hits only ever show and scale it
+pub fn spawn_health_bars(
+    mut commands: Commands,
+    monster_query: Query<Entity, (With<Monster>, Added<TextureAtlasSprite>)>,
+) {
+    for monster in &monster_query {
+        let bar = spawn_health_bar(&mut commands);
+        commands.entity(monster).add_child(bar);
+    }
+}
+
+pub fn update_health_bars(
+    mut combat_events: EventReader<CombatEvent>,
+    character_query: Query<(&Health, &Children), With<Monster>>,
+    mut bar_query: Query<(&mut Visibility, &Children), With<HealthBar>>,
+    mut fill_query: Query<&mut Transform, With<HealthBarFill>>,
+) {
+    for event in combat_events.read() {
+        let target = match *event {
+            CombatEvent::Damage { target, .. } | CombatEvent::Heal { target, .. } => target,
+            CombatEvent::Attack { .. } | CombatEvent::Miss { .. } => continue,
+        };
+        // the target may have died from the hit
+        let Ok((health, children)) = character_query.get(target) else {
+            continue;
+        };
+        let Some(bar) = children.iter().find(|child| bar_query.contains(**child)) else {
+            continue;
+        };
+        let Ok((mut visibility, fills)) = bar_query.get_mut(*bar) else {
+            continue;
+        };
+
+        // healthy monsters don't need a bar
+        let fraction = (health.current as f32 / health.max as f32).clamp(0.0, 1.0);
+        *visibility = if fraction < 1.0 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        for fill in fills.iter() {
+            if let Ok(mut transform) = fill_query.get_mut(*fill) {
+                transform.scale.x = fraction;
+            }
+        }
+    }
+}
+
+fn spawn_health_bar(commands: &mut Commands) -> Entity {
+    // the bar is a child of the sprite, so it follows its tweens and fog of war
+    let translation = Vec3::new(0.0, -BASE_GRID_SIZE * 0.5 + HEALTH_BAR_HEIGHT, 1.0);
+
+    commands
+        .spawn((
+            Name::new("health bar"),
+            HealthBar,
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(0.3, 0.05, 0.05),
+                    custom_size: Some(Vec2::new(HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT)),
+                    ..default()
+                },
+                transform: Transform::from_translation(translation),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                HealthBarFill,
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::rgb(0.8, 0.1, 0.1),
+                        custom_size: Some(Vec2::new(HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT)),
+                        anchor: Anchor::CenterLeft,
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(-HEALTH_BAR_WIDTH * 0.5, 0.0, 0.1),
+                    ..default()
+                },
+            ));
+        })
+        .id()
+}
@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+#[derive(Component, Debug)]
+pub struct AiDebugLabel {
+    pub owner: Entity,
+}
+
+#[derive(Component, Debug)]
+pub struct TargetingLabel;
+
+#[derive(Component, Debug)]
+pub struct MoveTween {
+    pub from: Vec3,
+    pub to: Vec3,
+    pub timer: Timer,
+}
+
+#[derive(Component, Debug)]
+pub struct BumpTween {
+    pub origin: Vec3,
+    pub direction: Vec3,
+    pub timer: Timer,
+}
+
+#[derive(Component, Debug)]
+pub struct DamageFlash {
+    pub timer: Timer,
+}
+
+#[derive(Component, Debug)]
+pub struct HudHealthFill;
+
+#[derive(Component, Debug)]
+pub struct HudHealthText;
+
+#[derive(Component, Debug)]
+pub struct HudStatsText;
+
+#[derive(Component, Debug)]
+pub struct HudDepthText;
+
+#[derive(Component, Debug)]
+pub struct HudStatusText;
+
+#[derive(Component, Debug)]
+pub struct HudInventoryText;
+
+#[derive(Component, Debug)]
+pub struct HudPerkPanel;
+
+#[derive(Component, Debug)]
+pub struct HudPerkText;
+
+#[derive(Component, Debug)]
+pub struct MinimapRoot;
+
+#[derive(Component, Debug)]
+pub struct MinimapImage;
+
+#[derive(Component, Debug)]
+pub struct HealthBar;
+
+#[derive(Component, Debug)]
+pub struct HealthBarFill;
+
+#[derive(Component, Debug)]
+pub struct FloatingText {
+    pub timer: Timer,
+}
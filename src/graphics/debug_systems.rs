@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::constants::*;
+use crate::game::components::Ai;
+use crate::graphics::components::AiDebugLabel;
+use crate::graphics::resources::AiDebugOverlay;
+
+pub fn toggle_ai_debug_overlay(
+    key_input: Res<Input<KeyCode>>,
+    mut ai_debug_overlay: ResMut<AiDebugOverlay>,
+) {
+    if key_input.just_pressed(KeyCode::F1) {
+        ai_debug_overlay.0 = !ai_debug_overlay.0;
+    }
+}
+
+pub fn update_ai_debug_labels(
+    mut commands: Commands,
+    ai_debug_overlay: Res<AiDebugOverlay>,
+    ai_query: Query<(Entity, &Ai, &Transform), Without<AiDebugLabel>>,
+    mut label_query: Query<(Entity, &AiDebugLabel, &mut Text, &mut Transform)>,
+) {
+    if !ai_debug_overlay.0 {
+        for (label_entity, _, _, _) in &label_query {
+            commands.entity(label_entity).despawn();
+        }
+        return;
+    }
+
+    let mut labelled = HashSet::new();
+
+    for (label_entity, label, mut text, mut transform) in &mut label_query {
+        let Ok((_, ai, owner_transform)) = ai_query.get(label.owner) else {
+            commands.entity(label_entity).despawn();
+            continue;
+        };
+
+        text.sections[0].value = ai_label(ai);
+        transform.translation = label_translation(owner_transform);
+        labelled.insert(label.owner);
+    }
+
+    for (entity, ai, transform) in &ai_query {
+        if labelled.contains(&entity) {
+            continue;
+        }
+
+        commands.spawn((
+            Name::new("ai debug label"),
+            AiDebugLabel { owner: entity },
+            Text2dBundle {
+                text: Text::from_section(
+                    ai_label(ai),
+                    TextStyle {
+                        font_size: 8.0,
+                        color: Color::YELLOW,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_translation(label_translation(transform))
+                    .with_scale(Vec3::splat(SCALE)),
+                ..default()
+            },
+        ));
+    }
+}
+
+fn ai_label(ai: &Ai) -> String {
+    format!("{:?}: {}", ai.kind, ai.state.label())
+}
+
+fn label_translation(owner_transform: &Transform) -> Vec3 {
+    owner_transform.translation + Vec3::new(0.0, GRID_SIZE * 0.75, 50.0)
+}
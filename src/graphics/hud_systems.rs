@@ -0,0 +1,328 @@
+use bevy::prelude::*;
+
+use crate::constants::HUD_HEIGHT;
+use crate::game::components::*;
+use crate::game::resources::{
+    DungeonDepth, GameTime, IdentifiedItems, ItemAppearances, PerkChoice,
+};
+use crate::game::templates::StatModifiers;
+use crate::graphics::components::*;
+
+const HUD_FONT_SIZE: f32 = 14.0;
+const HEALTH_BAR_WIDTH: f32 = 120.0;
+
+pub fn spawn_hud(mut commands: Commands) {
+    let text = |value: &str| {
+        TextBundle::from_section(
+            value,
+            TextStyle {
+                font_size: HUD_FONT_SIZE,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+    };
+
+    // a strip along the bottom edge, the camera keeps the player above it
+    commands
+        .spawn((
+            Name::new("hud"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(0.0),
+                    width: Val::Percent(100.0),
+                    height: Val::Px(HUD_HEIGHT),
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(16.0),
+                    padding: UiRect::horizontal(Val::Px(8.0)),
+                    ..default()
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.85).into(),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(HEALTH_BAR_WIDTH),
+                        height: Val::Px(HUD_FONT_SIZE + 4.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.3, 0.05, 0.05).into(),
+                    ..default()
+                })
+                .with_children(|bar| {
+                    bar.spawn((
+                        HudHealthFill,
+                        NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                left: Val::Px(0.0),
+                                width: Val::Percent(100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            background_color: Color::rgb(0.7, 0.1, 0.1).into(),
+                            ..default()
+                        },
+                    ));
+                    bar.spawn((HudHealthText, text("")));
+                });
+            parent.spawn((HudStatsText, text("")));
+            parent.spawn((HudDepthText, text("")));
+            parent.spawn((HudStatusText, text("")));
+        });
+
+    // the minimap owns the top right corner, so the pack is listed top left
+    commands
+        .spawn((
+            Name::new("hud inventory"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.0),
+                    left: Val::Px(0.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((HudInventoryText, text("")));
+        });
+
+    // input waits on the perk choice, so it is shown front and centre until made
+    commands
+        .spawn((
+            Name::new("hud perks"),
+            HudPerkPanel,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        padding: UiRect::all(Val::Px(12.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgba(0.0, 0.0, 0.0, 0.85).into(),
+                    ..default()
+                })
+                .with_children(|panel| {
+                    panel.spawn((HudPerkText, text("")));
+                });
+        });
+}
+
+pub fn update_hud_health(
+    player_query: Query<&Health, (With<PlayerControlled>, Changed<Health>)>,
+    mut fill_query: Query<&mut Style, With<HudHealthFill>>,
+    mut text_query: Query<&mut Text, With<HudHealthText>>,
+) {
+    let Ok(health) = player_query.get_single() else {
+        return;
+    };
+
+    let fraction = health.current.max(0) as f32 / health.max.max(1) as f32;
+    for mut style in &mut fill_query {
+        style.width = Val::Percent(fraction * 100.0);
+    }
+    for mut text in &mut text_query {
+        text.sections[0].value = format!("HP {}/{}", health.current, health.max);
+    }
+}
+
+type PlayerStatsQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Ref<'static, CombatStats>,
+        Ref<'static, Experience>,
+        Option<Ref<'static, Equipment>>,
+        Option<Ref<'static, Satiation>>,
+    ),
+    With<PlayerControlled>,
+>;
+
+pub fn update_hud_stats(
+    player_query: PlayerStatsQuery,
+    item_query: Query<&Item>,
+    mut text_query: Query<&mut Text, With<HudStatsText>>,
+) {
+    let Ok((stats, experience, equipment, satiation)) = player_query.get_single() else {
+        return;
+    };
+    let changed = stats.is_changed()
+        || experience.is_changed()
+        || equipment
+            .as_ref()
+            .is_some_and(|equipment| equipment.is_changed())
+        || satiation
+            .as_ref()
+            .is_some_and(|satiation| satiation.is_changed());
+    if !changed {
+        return;
+    }
+
+    let modifiers = StatModifiers::from_items(
+        equipment
+            .iter()
+            .flat_map(|equipment| equipment.items())
+            .filter_map(|item| item_query.get(item).ok())
+            .map(|item| item.kind),
+    );
+    let attack = stats.attack_with(&modifiers, satiation.as_deref());
+    let defense = stats.defense_with(&modifiers);
+
+    for mut text in &mut text_query {
+        text.sections[0].value = format!(
+            "ATK {}  DEF {}  LV {}  XP {}/{}",
+            attack,
+            defense,
+            experience.level,
+            experience.points,
+            experience.next_level_points()
+        );
+    }
+}
+
+pub fn update_hud_depth(
+    depth: Res<DungeonDepth>,
+    game_time: Res<GameTime>,
+    mut text_query: Query<&mut Text, With<HudDepthText>>,
+) {
+    if !depth.is_changed() && !game_time.is_changed() {
+        return;
+    }
+
+    for mut text in &mut text_query {
+        text.sections[0].value = format!("Depth {}  Turn {}", depth.0, game_time.0.floor());
+    }
+}
+
+type PlayerStatusQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Ref<'static, StatusEffects>, Option<Ref<'static, Satiation>>),
+    With<PlayerControlled>,
+>;
+
+pub fn update_hud_status(
+    player_query: PlayerStatusQuery,
+    mut text_query: Query<&mut Text, With<HudStatusText>>,
+) {
+    let Ok((status_effects, satiation)) = player_query.get_single() else {
+        return;
+    };
+    if !status_effects.is_changed() && !satiation.as_ref().is_some_and(|s| s.is_changed()) {
+        return;
+    }
+
+    let mut labels = status_effects
+        .iter()
+        .map(|effect| format!("{:?} {:.0}", effect.kind, effect.remaining.ceil()))
+        .collect::<Vec<String>>();
+    if let Some(satiation) = satiation {
+        if satiation.is_hungry() {
+            labels.push(format!("{:?}", satiation.state()));
+        }
+    }
+
+    for mut text in &mut text_query {
+        text.sections[0].value = labels.join("  ");
+    }
+}
+
+type PlayerInventoryQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Ref<'static, Inventory>, Option<Ref<'static, Equipment>>),
+    With<PlayerControlled>,
+>;
+
+pub fn update_hud_inventory(
+    player_query: PlayerInventoryQuery,
+    item_query: Query<&Item>,
+    appearances: Res<ItemAppearances>,
+    identified: Res<IdentifiedItems>,
+    mut text_query: Query<&mut Text, With<HudInventoryText>>,
+) {
+    let Ok((inventory, equipment)) = player_query.get_single() else {
+        return;
+    };
+    let changed = inventory.is_changed()
+        || equipment
+            .as_ref()
+            .is_some_and(|equipment| equipment.is_changed())
+        || identified.is_changed();
+    if !changed {
+        return;
+    }
+
+    // numbered like the slot keys used to throw, use and equip
+    let lines = inventory
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| {
+            let kind = item_query.get(*item).ok()?.kind;
+            let equipped = equipment
+                .as_ref()
+                .is_some_and(|equipment| equipment.contains(*item));
+            Some(format!(
+                "{}) {}{}",
+                index + 1,
+                appearances.name_of(kind, &identified),
+                if equipped { " (equipped)" } else { "" }
+            ))
+        })
+        .collect::<Vec<String>>();
+
+    for mut text in &mut text_query {
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+pub fn update_hud_perks(
+    perk_choice: Option<Res<PerkChoice>>,
+    mut panel_query: Query<&mut Visibility, With<HudPerkPanel>>,
+    mut text_query: Query<&mut Text, With<HudPerkText>>,
+) {
+    let visibility = if perk_choice.is_some() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    for mut panel_visibility in &mut panel_query {
+        if *panel_visibility != visibility {
+            *panel_visibility = visibility;
+        }
+    }
+
+    let Some(perk_choice) = perk_choice.filter(|perk_choice| perk_choice.is_changed()) else {
+        return;
+    };
+
+    let mut lines = vec!["Level up! Choose a perk:".to_string()];
+    for (index, perk) in perk_choice.options.iter().enumerate() {
+        lines.push(format!("{}) {}", index + 1, perk.label()));
+    }
+    for mut text in &mut text_query {
+        text.sections[0].value = lines.join("\n");
+    }
+}
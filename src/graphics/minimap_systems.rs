@@ -0,0 +1,252 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::window::PrimaryWindow;
+
+use crate::constants::*;
+use crate::game::components::*;
+use crate::game::resources::{ExploredTiles, LightMap, VisibleTiles};
+use crate::game::services::lighting;
+use crate::game::vector::GridVector;
+use crate::graphics::components::{MinimapImage, MinimapRoot};
+use crate::graphics::resources::{LevelBounds, MinimapTexture, MinimapView};
+
+const MINIMAP_PIXEL_SIZE: f32 = 2.0;
+const MINIMAP_MARGIN: f32 = 8.0;
+// the full screen map leaves a little of the level view showing around it
+const FULL_SCREEN_FILL: f32 = 0.9;
+
+const FLOOR_COLOR: [u8; 4] = [90, 90, 100, 255];
+const WALL_COLOR: [u8; 4] = [170, 160, 140, 255];
+const DOOR_COLOR: [u8; 4] = [160, 100, 40, 255];
+const STAIRS_COLOR: [u8; 4] = [240, 220, 60, 255];
+const MONSTER_COLOR: [u8; 4] = [230, 50, 50, 255];
+const PLAYER_COLOR: [u8; 4] = [80, 230, 80, 255];
+
+pub fn spawn_minimap(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let image = images.add(Image::new_fill(
+        Extent3d::default(),
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+    ));
+
+    commands
+        .spawn((
+            Name::new("minimap"),
+            MinimapRoot,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    padding: UiRect::all(Val::Px(MINIMAP_MARGIN)),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                MinimapImage,
+                ImageBundle {
+                    image: UiImage::new(image.clone()),
+                    ..default()
+                },
+            ));
+        });
+
+    commands.insert_resource(MinimapTexture {
+        image,
+        origin: GridVector::new(0, 0),
+        size: UVec2::ONE,
+    });
+}
+
+pub fn toggle_minimap_view(key_input: Res<Input<KeyCode>>, mut minimap_view: ResMut<MinimapView>) {
+    if key_input.just_pressed(KeyCode::M) {
+        minimap_view.full_screen = !minimap_view.full_screen;
+    }
+}
+
+// what the player knows of the current level
+#[derive(SystemParam)]
+pub struct LevelKnowledge<'w> {
+    level_bounds: Res<'w, LevelBounds>,
+    visible_tiles: Res<'w, VisibleTiles>,
+    explored_tiles: Res<'w, ExploredTiles>,
+    light_map: Res<'w, LightMap>,
+}
+
+type MinimapTileQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static GridPosition,
+        Has<Floor>,
+        Has<Solid>,
+        Has<Stairs>,
+        Option<&'static Door>,
+    ),
+    (With<MapEntity>, Without<Character>, Without<Item>),
+>;
+
+type MinimapChangeQuery<'w, 's> =
+    Query<'w, 's, (), Or<(Changed<Door>, (With<Character>, Changed<GridPosition>))>>;
+
+pub fn update_minimap_texture(
+    mut minimap: ResMut<MinimapTexture>,
+    mut images: ResMut<Assets<Image>>,
+    knowledge: LevelKnowledge,
+    tile_query: MinimapTileQuery,
+    character_query: Query<(&GridPosition, Has<PlayerControlled>), With<Character>>,
+    changed_query: MinimapChangeQuery,
+) {
+    let LevelKnowledge {
+        level_bounds,
+        visible_tiles,
+        explored_tiles,
+        light_map,
+    } = knowledge;
+    if !level_bounds.is_changed()
+        && !visible_tiles.is_changed()
+        && !explored_tiles.is_changed()
+        && !light_map.is_changed()
+        && changed_query.is_empty()
+    {
+        return;
+    }
+    let Some(bounds) = level_bounds.0 else {
+        return;
+    };
+
+    // level bounds run along the tile edges, the grid is on the tile centres
+    let min = ((bounds.min + GRID_SIZE * 0.5) / GRID_SIZE).round();
+    let max = ((bounds.max - GRID_SIZE * 0.5) / GRID_SIZE).round();
+    let origin = GridVector::new(min.x as i32, max.y as i32);
+    let size = UVec2::new((max.x - min.x) as u32 + 1, (max.y - min.y) as u32 + 1);
+    if minimap.origin != origin || minimap.size != size {
+        minimap.origin = origin;
+        minimap.size = size;
+    }
+
+    let Some(image) = images.get_mut(&minimap.image) else {
+        return;
+    };
+    let extent = Extent3d {
+        width: size.x,
+        height: size.y,
+        depth_or_array_layers: 1,
+    };
+    if image.texture_descriptor.size != extent {
+        image.resize(extent);
+    }
+    image.data.fill(0);
+
+    let pixel_offset = |coordinates: GridVector| {
+        let column = coordinates.x - origin.x;
+        let row = origin.y - coordinates.y;
+        let inside = (0..size.x as i32).contains(&column) && (0..size.y as i32).contains(&row);
+        inside.then(|| (row as usize * size.x as usize + column as usize) * 4)
+    };
+    let mut paint = |coordinates: GridVector, color: [u8; 4]| {
+        let Some(offset) = pixel_offset(coordinates) else {
+            return;
+        };
+        // remembered tiles are drawn darker than the ones in view
+        let color = if visible_tiles.contains(&coordinates) {
+            color
+        } else {
+            [color[0] / 2, color[1] / 2, color[2] / 2, color[3]]
+        };
+        image.data[offset..offset + 4].copy_from_slice(&color);
+    };
+
+    let mut tiles = tile_query
+        .iter()
+        .filter(|(position, ..)| explored_tiles.contains(&position.coordinates))
+        .filter_map(|(position, floor, solid, stairs, door)| {
+            let (layer, color) = match door {
+                Some(door) if door.secret => (1, WALL_COLOR),
+                Some(_) => (2, DOOR_COLOR),
+                None if stairs => (3, STAIRS_COLOR),
+                None if solid => (1, WALL_COLOR),
+                None if floor => (0, FLOOR_COLOR),
+                None => return None,
+            };
+            Some((layer, position.coordinates, color))
+        })
+        .collect::<Vec<(u8, GridVector, [u8; 4])>>();
+    tiles.sort_by_key(|(layer, ..)| *layer);
+    for (_, coordinates, color) in tiles {
+        paint(coordinates, color);
+    }
+
+    let viewer = character_query
+        .iter()
+        .find(|(_, is_player)| *is_player)
+        .map(|(position, _)| position.coordinates);
+    for (position, is_player) in &character_query {
+        if is_player {
+            paint(position.coordinates, PLAYER_COLOR);
+        } else if visible_tiles.contains(&position.coordinates)
+            && viewer.is_some_and(|viewer| {
+                lighting::is_lit_for(viewer, position.coordinates, &light_map)
+            })
+        {
+            paint(position.coordinates, MONSTER_COLOR);
+        }
+    }
+}
+
+pub fn update_minimap_layout(
+    minimap: Res<MinimapTexture>,
+    minimap_view: Res<MinimapView>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+    mut root_query: Query<(&mut Style, &mut BackgroundColor), With<MinimapRoot>>,
+    mut image_query: Query<&mut Style, (With<MinimapImage>, Without<MinimapRoot>)>,
+) {
+    let (Ok(window), Ok((mut root_style, mut background)), Ok(mut image_style)) = (
+        window_query.get_single(),
+        root_query.get_single_mut(),
+        image_query.get_single_mut(),
+    ) else {
+        return;
+    };
+
+    let size = minimap.size.as_vec2();
+    let pixel_size = if minimap_view.full_screen {
+        // whole screen pixels per tile keep the full map crisp
+        let available =
+            Vec2::new(window.width(), window.height()) * FULL_SCREEN_FILL / ui_scale.0 as f32;
+        (available / size).min_element().floor().max(1.0)
+    } else {
+        MINIMAP_PIXEL_SIZE
+    };
+
+    let (justify_content, align_items, color) = if minimap_view.full_screen {
+        (
+            JustifyContent::Center,
+            AlignItems::Center,
+            Color::rgba(0.0, 0.0, 0.0, 0.85),
+        )
+    } else {
+        (JustifyContent::End, AlignItems::Start, Color::NONE)
+    };
+    let width = Val::Px(size.x * pixel_size);
+    let height = Val::Px(size.y * pixel_size);
+
+    // only touch the styles when something moved, so the ui isn't laid out every frame
+    if image_style.width != width || image_style.height != height {
+        image_style.width = width;
+        image_style.height = height;
+    }
+    if root_style.justify_content != justify_content || root_style.align_items != align_items {
+        root_style.justify_content = justify_content;
+        root_style.align_items = align_items;
+    }
+    if background.0 != color {
+        background.0 = color;
+    }
+}
@@ -1,20 +1,84 @@
 use bevy::prelude::*;
 
+use crate::graphics::animation_systems::*;
+use crate::graphics::combat_text_systems::*;
+use crate::graphics::debug_systems::*;
+use crate::graphics::hud_systems::*;
+use crate::graphics::minimap_systems::*;
+use crate::graphics::resources::{
+    AiDebugOverlay, CameraZoom, LevelBounds, MinimapView, WallTileMapping,
+};
 use crate::graphics::systems::*;
+use crate::states::MainState;
 
+mod animation_systems;
+mod combat_text_systems;
+mod components;
+mod debug_systems;
+mod hud_systems;
+mod minimap_systems;
+pub mod resources;
 mod systems;
 
 pub struct GraphicsPlugin;
 
 impl Plugin for GraphicsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (
-                spawn_game_entity,
-                update_game_entity_graphics,
-                update_camera_position.after(update_game_entity_graphics),
-            ),
-        );
+        app.init_resource::<AiDebugOverlay>()
+            .init_resource::<WallTileMapping>()
+            .init_resource::<CameraZoom>()
+            .init_resource::<LevelBounds>()
+            .init_resource::<MinimapView>()
+            .add_systems(
+                Update,
+                (
+                    spawn_game_entity,
+                    warn_missing_renderables,
+                    update_door_renderables.before(update_game_entity_graphics),
+                    update_game_entity_graphics,
+                    update_wall_tiles.after(update_game_entity_graphics),
+                    update_camera_zoom,
+                    update_level_bounds,
+                    update_camera_position
+                        .after(update_camera_zoom)
+                        .after(update_level_bounds),
+                    update_item_visibility,
+                    update_fog_of_war.after(update_item_visibility),
+                    draw_targeting_preview,
+                    update_targeting_label,
+                    toggle_ai_debug_overlay,
+                    update_ai_debug_labels.after(update_game_entity_graphics),
+                    (
+                        start_move_tweens,
+                        start_combat_animations,
+                        animate_moves,
+                        animate_bumps.after(animate_moves),
+                        animate_damage_flashes.after(update_fog_of_war),
+                    ),
+                ),
+            )
+            .add_systems(OnEnter(MainState::Game), (spawn_hud, spawn_minimap))
+            .add_systems(
+                Update,
+                (
+                    update_hud_health,
+                    update_hud_stats,
+                    update_hud_depth,
+                    update_hud_status,
+                    update_hud_inventory,
+                    update_hud_perks,
+                    spawn_floating_text,
+                    animate_floating_text,
+                    spawn_health_bars,
+                    update_health_bars.after(spawn_health_bars),
+                    toggle_minimap_view,
+                    update_minimap_texture.after(update_level_bounds),
+                    update_minimap_layout
+                        .after(toggle_minimap_view)
+                        .after(update_minimap_texture),
+                )
+                    .run_if(in_state(MainState::Game)),
+            )
+            .add_systems(PostUpdate, update_animation_lock);
     }
 }
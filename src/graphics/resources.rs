@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::constants::SCALE;
+use crate::game::vector::GridVector;
+
+#[derive(Resource, Default, PartialEq)]
+pub struct AiDebugOverlay(pub bool);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Neighbourhood {
+    Four,
+    Eight,
+}
+
+/// Maps a wall's neighbour bitmask to a tileset index.
+/// Bits: N 1, E 2, S 4, W 8 and, for `Eight`, NE 16, SE 32, SW 64, NW 128.
+#[derive(Resource)]
+pub struct WallTileMapping {
+    pub neighbourhood: Neighbourhood,
+    pub sprites: HashMap<u8, usize>,
+    pub fallback: usize,
+}
+
+impl WallTileMapping {
+    pub fn sprite_index(&self, mask: u8) -> usize {
+        self.sprites.get(&mask).copied().unwrap_or(self.fallback)
+    }
+}
+
+impl Default for WallTileMapping {
+    fn default() -> Self {
+        // walls with open floor to the south show their face, lone walls stand as pillars
+        let mut sprites = (0..16)
+            .filter(|mask| mask & 4 == 0)
+            .map(|mask| (mask, 6))
+            .collect::<HashMap<u8, usize>>();
+        sprites.insert(0, 7);
+
+        Self {
+            neighbourhood: Neighbourhood::Four,
+            sprites,
+            fallback: 1,
+        }
+    }
+}
+
+pub const MIN_ZOOM: u32 = 1;
+pub const MAX_ZOOM: u32 = 4;
+
+/// Whole screen pixels per tileset pixel, kept integral so sprites stay crisp.
+#[derive(Resource)]
+pub struct CameraZoom(pub u32);
+
+impl Default for CameraZoom {
+    fn default() -> Self {
+        Self(SCALE as u32)
+    }
+}
+
+impl CameraZoom {
+    pub fn step(&mut self, steps: i32) {
+        self.0 = self
+            .0
+            .saturating_add_signed(steps)
+            .clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+}
+
+/// World space rectangle covered by the current level's tiles.
+#[derive(Resource, Default, PartialEq)]
+pub struct LevelBounds(pub Option<Rect>);
+
+/// The minimap texture, one pixel per tile of the current level.
+#[derive(Resource)]
+pub struct MinimapTexture {
+    pub image: Handle<Image>,
+    // the tile drawn in the top left pixel
+    pub origin: GridVector,
+    pub size: UVec2,
+}
+
+#[derive(Resource, Default, PartialEq)]
+pub struct MinimapView {
+    pub full_screen: bool,
+}
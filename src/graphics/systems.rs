@@ -1,74 +1,102 @@
+use bevy::ecs::system::SystemParam;
+use bevy::input::mouse::MouseWheel;
 use bevy::prelude::*;
+use bevy::utils::HashSet;
+use bevy::window::PrimaryWindow;
 
 use crate::assets::resources::Tileset;
 use crate::constants::*;
 use crate::game::components::*;
 use crate::game::directions::GridDirection;
+use crate::game::resources::{ExploredTiles, LightMap, Targeting, VisibleTiles};
+use crate::game::services::lighting;
+use crate::game::templates;
 use crate::game::vector::GridVector;
+use crate::graphics::components::TargetingLabel;
+use crate::graphics::resources::{CameraZoom, LevelBounds, Neighbourhood, WallTileMapping};
+
+// how quickly the camera closes the distance to the player, per second
+const CAMERA_EASING: f32 = 10.0;
+
+type NewRenderableQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static GridPosition, &'static Renderable),
+    (
+        With<MapEntity>,
+        Added<GridPosition>,
+        Without<TextureAtlasSprite>,
+    ),
+>;
 
 pub fn spawn_game_entity(
     mut commands: Commands,
-    entity_query: Query<(Entity, &Name, &GridPosition, Option<&Door>), Added<MapEntity>>,
+    entity_query: NewRenderableQuery,
     tileset: Res<Tileset>,
 ) {
-    for (entity, name, grid_position, door) in &entity_query {
-        let mut insert_graphics = |layer: f32, sprite_index: usize| {
-            commands.entity(entity).insert(spawn_sprite_sheet_bundle(
-                grid_position.coordinates,
-                layer,
-                sprite_index,
-                &tileset.0,
-            ));
-        };
+    for (entity, grid_position, renderable) in &entity_query {
+        commands.entity(entity).insert(spawn_sprite_sheet_bundle(
+            grid_position.coordinates,
+            renderable,
+            &tileset.0,
+        ));
+    }
+}
 
-        match name.as_str() {
-            "floor" => insert_graphics(0.0, 0),
-            "wall" => insert_graphics(10.0, 1),
-            "door" => {
-                if let Some(door) = door {
-                    let sprite_index = if door.closed { 3 } else { 2 };
-                    insert_graphics(10.0, sprite_index)
-                }
-            }
-            "player" => insert_graphics(20.0, 8),
-            "monster" => insert_graphics(20.0, 9),
-            _ => {}
-        }
+type MissingRenderableQuery<'w, 's> =
+    Query<'w, 's, (Entity, Option<&'static Name>), (Added<MapEntity>, Without<Renderable>)>;
+
+pub fn warn_missing_renderables(entity_query: MissingRenderableQuery) {
+    for (entity, name) in &entity_query {
+        warn!(
+            "map entity {:?} ({:?}) has no Renderable and will not be drawn",
+            entity, name
+        );
     }
 }
 
 fn spawn_sprite_sheet_bundle(
     coordinates: GridVector,
-    layer: f32,
-    sprite_index: usize,
+    renderable: &Renderable,
     tileset: &Handle<TextureAtlas>,
 ) -> SpriteSheetBundle {
-    let translation = coordinates.to_vec3(layer);
+    let translation = coordinates.to_vec3(renderable.layer);
+
+    let mut sprite = TextureAtlasSprite::new(renderable.sprite_index);
+    sprite.color = renderable.tint;
 
     SpriteSheetBundle {
         texture_atlas: tileset.clone(),
-        sprite: TextureAtlasSprite::new(sprite_index),
+        sprite,
         transform: Transform::from_translation(translation).with_scale(Vec3::splat(SCALE)),
         ..default()
     }
 }
 
-pub fn update_game_entity_graphics(
-    mut entity_query: Query<
-        (
-            &mut Transform,
-            &mut TextureAtlasSprite,
-            &GridPosition,
-            Option<&Door>,
-        ),
-        Or<(Changed<GridPosition>, Changed<Door>)>,
-    >,
-) {
-    for (mut transform, mut sprite, position, door) in &mut entity_query {
-        let new_translation = position.coordinates.to_vec3(transform.translation.z);
-        transform.translation = new_translation;
+pub fn update_door_renderables(mut door_query: Query<(&Door, &mut Renderable), Changed<Door>>) {
+    for (door, mut renderable) in &mut door_query {
+        renderable.sprite_index = templates::door_sprite_index(door);
+    }
+}
+
+type RenderableGraphicsQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Transform,
+        &'static mut TextureAtlasSprite,
+        &'static GridPosition,
+        Ref<'static, Renderable>,
+    ),
+    Or<(Changed<GridPosition>, Changed<Renderable>)>,
+>;
+
+pub fn update_game_entity_graphics(mut entity_query: RenderableGraphicsQuery) {
+    // the translation itself is animated by `start_move_tweens`
+    for (mut transform, mut sprite, position, renderable) in &mut entity_query {
+        transform.translation.z = renderable.layer;
 
-        if let Some(direction) = position.direction {
+        if let (FlipRule::FaceDirection, Some(direction)) = (renderable.flip, position.direction) {
             match direction {
                 GridDirection::NorthEast | GridDirection::East | GridDirection::SouthEast => {
                     sprite.flip_x = false;
@@ -80,20 +108,354 @@ pub fn update_game_entity_graphics(
             }
         }
 
-        if let Some(door) = door {
-            let sprite_index = if door.closed { 3 } else { 2 };
-            sprite.index = sprite_index;
+        if renderable.is_changed() {
+            sprite.index = renderable.sprite_index;
+        }
+    }
+}
+
+type WallQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static GridPosition,
+        &'static mut TextureAtlasSprite,
+        Ref<'static, Renderable>,
+        Option<&'static Door>,
+    ),
+    (With<MapEntity>, With<Solid>, Without<Character>),
+>;
+
+pub fn update_wall_tiles(wall_tile_mapping: Res<WallTileMapping>, mut wall_query: WallQuery) {
+    let changed = wall_query
+        .iter_mut()
+        .any(|(_, sprite, renderable, _)| sprite.is_added() || renderable.is_changed());
+    if !wall_tile_mapping.is_changed() && !changed {
+        return;
+    }
+
+    let is_wall = |door: Option<&Door>| door.is_none_or(|door| door.secret);
+    let walls = wall_query
+        .iter()
+        .filter(|(_, _, _, door)| is_wall(*door))
+        .map(|(position, _, _, _)| position.coordinates)
+        .collect::<HashSet<GridVector>>();
+
+    for (position, mut sprite, _, door) in &mut wall_query {
+        if is_wall(door) {
+            let mask = wall_mask(
+                position.coordinates,
+                &walls,
+                wall_tile_mapping.neighbourhood,
+            );
+            sprite.index = wall_tile_mapping.sprite_index(mask);
+        }
+    }
+}
+
+fn wall_mask(
+    coordinates: GridVector,
+    walls: &HashSet<GridVector>,
+    neighbourhood: Neighbourhood,
+) -> u8 {
+    let has_wall = |direction: GridDirection| {
+        walls.contains(&(coordinates + GridVector::from_direction(&direction)))
+    };
+
+    let north = has_wall(GridDirection::North);
+    let east = has_wall(GridDirection::East);
+    let south = has_wall(GridDirection::South);
+    let west = has_wall(GridDirection::West);
+    let mut mask = north as u8 | (east as u8) << 1 | (south as u8) << 2 | (west as u8) << 3;
+
+    // a corner only counts when both of its sides are walls too
+    if neighbourhood == Neighbourhood::Eight {
+        let corners = [
+            (GridDirection::NorthEast, north && east),
+            (GridDirection::SouthEast, south && east),
+            (GridDirection::SouthWest, south && west),
+            (GridDirection::NorthWest, north && west),
+        ];
+        for (bit, (direction, sides)) in corners.into_iter().enumerate() {
+            if sides && has_wall(direction) {
+                mask |= 1 << (4 + bit);
+            }
         }
     }
+
+    mask
+}
+
+pub fn update_camera_zoom(
+    mut wheel_events: EventReader<MouseWheel>,
+    key_input: Res<Input<KeyCode>>,
+    mut zoom: ResMut<CameraZoom>,
+    mut camera_query: Query<&mut OrthographicProjection, With<Camera>>,
+) {
+    let mut steps = wheel_events
+        .read()
+        .filter(|event| event.y != 0.0)
+        .map(|event| event.y.signum() as i32)
+        .sum::<i32>();
+    if key_input.any_just_pressed([KeyCode::Equals, KeyCode::NumpadAdd]) {
+        steps += 1;
+    }
+    if key_input.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        steps -= 1;
+    }
+    if steps != 0 {
+        zoom.step(steps);
+    }
+
+    if zoom.is_changed() {
+        let mut projection = camera_query.single_mut();
+        projection.scale = 1.0 / zoom.0 as f32;
+    }
+}
+
+// the level's own tiles, whatever is standing or lying on them does not count
+type LevelTileFilter = (With<MapEntity>, Without<Character>, Without<Item>);
+
+pub fn update_level_bounds(
+    mut level_bounds: ResMut<LevelBounds>,
+    mut removed_positions: RemovedComponents<GridPosition>,
+    tile_query: Query<&GridPosition, LevelTileFilter>,
+    changed_query: Query<(), (LevelTileFilter, Changed<GridPosition>)>,
+) {
+    if removed_positions.read().count() == 0 && changed_query.is_empty() {
+        return;
+    }
+
+    let bounds = tile_query
+        .iter()
+        .map(|position| {
+            Rect::from_center_size(
+                position.coordinates.to_vec3(0.0).truncate(),
+                Vec2::splat(GRID_SIZE),
+            )
+        })
+        .reduce(|bounds, tile| bounds.union(tile));
+    level_bounds.set_if_neq(LevelBounds(bounds));
+}
+
+// what the camera has to fit into the window
+#[derive(SystemParam)]
+pub struct CameraView<'w, 's> {
+    window_query: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    level_bounds: Res<'w, LevelBounds>,
+    zoom: Res<'w, CameraZoom>,
+    ui_scale: Res<'w, UiScale>,
 }
 
 pub fn update_camera_position(
-    player_query: Query<&GridPosition, (Changed<GridPosition>, With<PlayerControlled>)>,
-    mut camera_query: Query<&mut Transform, With<Camera>>,
+    player_query: Query<&GridPosition, With<PlayerControlled>>,
+    mut camera_query: Query<(&mut Transform, &OrthographicProjection), With<Camera>>,
+    view: CameraView,
+    time: Res<Time>,
+    mut focus: Local<Option<Vec2>>,
+) {
+    let (Ok(grid_position), Ok(window)) =
+        (player_query.get_single(), view.window_query.get_single())
+    else {
+        return;
+    };
+    let (mut transform, projection) = camera_query.single_mut();
+    let (level_bounds, zoom, ui_scale) = (&view.level_bounds, &view.zoom, &view.ui_scale);
+
+    // only the part of the window above the hud shows the map
+    let hud_height = (HUD_HEIGHT * ui_scale.0 as f32).min(window.height());
+    let hud_offset = hud_height * 0.5 * projection.scale;
+
+    let mut target = grid_position.coordinates.to_vec3(0.0).truncate();
+    if let Some(bounds) = level_bounds.0 {
+        let half_view =
+            Vec2::new(window.width(), window.height() - hud_height) * 0.5 * projection.scale;
+        target.x = clamp_to_bounds(target.x, bounds.min.x, bounds.max.x, half_view.x);
+        target.y = clamp_to_bounds(target.y, bounds.min.y, bounds.max.y, half_view.y);
+    }
+
+    // jump straight to a new level instead of panning across the void
+    let current = match *focus {
+        Some(current) if !level_bounds.is_changed() => current,
+        _ => target,
+    };
+    let easing = 1.0 - (-CAMERA_EASING * time.delta_seconds()).exp();
+    let current = current.lerp(target, easing);
+    *focus = Some(current);
+
+    // snap to whole screen pixels so the tiles don't shimmer while easing
+    let pixel = 1.0 / zoom.0 as f32;
+    transform.translation.x = (current.x / pixel).round() * pixel;
+    transform.translation.y = ((current.y - hud_offset) / pixel).round() * pixel;
+}
+
+// levels smaller than the view are centred rather than clamped
+fn clamp_to_bounds(value: f32, min: f32, max: f32, half_view: f32) -> f32 {
+    if max - min <= half_view * 2.0 {
+        (min + max) * 0.5
+    } else {
+        value.clamp(min + half_view, max - half_view)
+    }
+}
+
+pub fn update_item_visibility(
+    mut removed_positions: RemovedComponents<GridPosition>,
+    mut item_query: Query<&mut Visibility, With<Item>>,
+    placed_item_query: Query<Entity, (With<Item>, Added<GridPosition>)>,
+) {
+    for entity in removed_positions.read() {
+        if let Ok(mut visibility) = item_query.get_mut(entity) {
+            *visibility = Visibility::Hidden;
+        }
+    }
+
+    for entity in &placed_item_query {
+        if let Ok(mut visibility) = item_query.get_mut(entity) {
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
+
+type FogQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static GridPosition,
+        &'static mut Visibility,
+        &'static mut TextureAtlasSprite,
+        &'static Renderable,
+        Has<Character>,
+        Has<Item>,
+        Option<Ref<'static, Trap>>,
+    ),
+    With<MapEntity>,
+>;
+
+pub fn update_fog_of_war(
+    visible_tiles: Res<VisibleTiles>,
+    explored_tiles: Res<ExploredTiles>,
+    light_map: Res<LightMap>,
+    player_query: Query<&GridPosition, With<PlayerControlled>>,
+    mut entity_query: FogQuery,
+) {
+    let changed = entity_query
+        .iter_mut()
+        .any(|(_, _, sprite, _, _, _, trap)| {
+            sprite.is_added() || trap.is_some_and(|trap| trap.is_changed())
+        });
+    if !visible_tiles.is_changed()
+        && !explored_tiles.is_changed()
+        && !light_map.is_changed()
+        && !changed
+    {
+        return;
+    }
+
+    let viewer = player_query
+        .get_single()
+        .map_or(GridVector::new(0, 0), |position| position.coordinates);
+
+    for (position, mut visibility, mut sprite, renderable, is_character, is_item, trap) in
+        &mut entity_query
+    {
+        let visible = visible_tiles.contains(&position.coordinates);
+        let explored = explored_tiles.contains(&position.coordinates);
+        // characters standing in the dark are not seen even in line of sight
+        let hidden = trap.is_some_and(|trap| trap.hidden)
+            || (is_character && !lighting::is_lit_for(viewer, position.coordinates, &light_map));
+
+        // characters and items are only shown while in view, map tiles stay remembered
+        *visibility = if hidden {
+            Visibility::Hidden
+        } else if visible || (explored && !is_character && !is_item) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        sprite.color = shaded_color(
+            renderable.tint,
+            position.coordinates,
+            &visible_tiles,
+            &light_map,
+        );
+    }
+}
+
+// the resting colour of a sprite, lit while in view and dimmed once only remembered
+pub fn shaded_color(
+    tint: Color,
+    coordinates: GridVector,
+    visible_tiles: &VisibleTiles,
+    light_map: &LightMap,
+) -> Color {
+    if visible_tiles.contains(&coordinates) {
+        tint * light_at(coordinates, light_map)
+    } else {
+        tint * Vec3::splat(0.5)
+    }
+}
+
+fn light_at(coordinates: GridVector, light_map: &LightMap) -> Vec3 {
+    let light = light_map.get(&coordinates).copied().unwrap_or(Color::BLACK);
+    (Vec3::new(light.r(), light.g(), light.b()) + Vec3::splat(lighting::AMBIENT_LIGHT))
+        .min(Vec3::ONE)
+}
+
+pub fn draw_targeting_preview(targeting: Option<Res<Targeting>>, mut gizmos: Gizmos) {
+    let Some(targeting) = targeting else {
+        return;
+    };
+
+    for coordinates in &targeting.path {
+        gizmos.rect_2d(
+            coordinates.to_vec3(0.0).truncate(),
+            0.0,
+            Vec2::splat(GRID_SIZE * 0.5),
+            Color::ORANGE,
+        );
+    }
+
+    gizmos.rect_2d(
+        targeting.cursor.to_vec3(0.0).truncate(),
+        0.0,
+        Vec2::splat(GRID_SIZE),
+        Color::RED,
+    );
+}
+
+pub fn update_targeting_label(
+    mut commands: Commands,
+    targeting: Option<Res<Targeting>>,
+    mut label_query: Query<(Entity, &mut Text, &mut Transform), With<TargetingLabel>>,
 ) {
-    if let Ok(grid_position) = player_query.get_single() {
-        let mut transform = camera_query.single_mut();
-        transform.translation.x = grid_position.coordinates.vec_x();
-        transform.translation.y = grid_position.coordinates.vec_y();
+    let Some(targeting) = targeting else {
+        for (label_entity, _, _) in &label_query {
+            commands.entity(label_entity).despawn();
+        }
+        return;
+    };
+
+    let translation = targeting.cursor.to_vec3(60.0) + Vec3::new(0.0, GRID_SIZE, 0.0);
+
+    if let Ok((_, mut text, mut transform)) = label_query.get_single_mut() {
+        text.sections[0].value = targeting.description.clone();
+        transform.translation = translation;
+    } else {
+        commands.spawn((
+            Name::new("targeting label"),
+            TargetingLabel,
+            Text2dBundle {
+                text: Text::from_section(
+                    targeting.description.clone(),
+                    TextStyle {
+                        font_size: 8.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_translation(translation).with_scale(Vec3::splat(SCALE)),
+                ..default()
+            },
+        ));
     }
 }
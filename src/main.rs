@@ -1,19 +1,30 @@
 use bevy::prelude::*;
 
+mod args;
 mod assets;
 mod constants;
 mod game;
 mod graphics;
+mod settings_file;
 mod setup;
 mod states;
+mod terminal;
 
 fn main() {
-    App::new()
-        .add_plugins((
+    let args = args::LaunchArgs::parse();
+    let mut app = App::new();
+    app.insert_resource(args.seed).insert_resource(args.mode);
+
+    if args.terminal {
+        app.add_plugins((terminal::TerminalPlugin, game::GamePlugin));
+    } else {
+        app.add_plugins((
             setup::SetupPlugin,
             assets::AssetsPlugin,
             game::GamePlugin,
             graphics::GraphicsPlugin,
-        ))
-        .run();
+        ));
+    }
+
+    app.run();
 }
@@ -0,0 +1,48 @@
+use std::fs;
+
+use bevy::prelude::*;
+
+/// Reads the `key = value` lines of a settings file, skipping blank lines and `#` comments.
+pub fn read_entries(path: &str) -> Vec<(String, String)> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let Some((key, value)) = line.split_once('=') else {
+                warn!("ignoring malformed settings line {:?}", line);
+                return None;
+            };
+            Some((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+// lines owned by other settings are kept as they were
+pub fn write_entries(path: &str, entries: &[(&str, String)]) {
+    let mut lines = Vec::new();
+    let mut written = vec![false; entries.len()];
+    for line in fs::read_to_string(path).unwrap_or_default().lines() {
+        let key = line.split_once('=').map(|(key, _)| key.trim());
+        match entries.iter().position(|(other, _)| Some(*other) == key) {
+            Some(index) => {
+                lines.push(format!("{} = {}", entries[index].0, entries[index].1));
+                written[index] = true;
+            }
+            None => lines.push(line.to_string()),
+        }
+    }
+    for ((key, value), _) in entries.iter().zip(written).filter(|(_, written)| !written) {
+        lines.push(format!("{} = {}", key, value));
+    }
+
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    if let Err(error) = fs::write(path, contents) {
+        error!("could not save settings to {}: {}", path, error);
+    }
+}
@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+use crate::setup::resources::OptionsEntry;
+
+#[derive(Component)]
+pub struct OptionsMenuRoot;
+
+#[derive(Component)]
+pub struct OptionsMenuItem(pub OptionsEntry);
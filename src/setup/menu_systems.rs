@@ -0,0 +1,154 @@
+use bevy::app::AppExit;
+use bevy::prelude::*;
+
+use crate::constants::SETTINGS_PATH;
+use crate::game::resources::GamePaused;
+use crate::setup::components::{OptionsMenuItem, OptionsMenuRoot};
+use crate::setup::resources::{DisplaySettings, OptionsEntry, OptionsMenu};
+
+const SELECTED_COLOR: Color = Color::YELLOW;
+const ITEM_COLOR: Color = Color::WHITE;
+
+pub fn toggle_options_menu(
+    key_input: Res<Input<KeyCode>>,
+    mut options_menu: ResMut<OptionsMenu>,
+    mut game_paused: ResMut<GamePaused>,
+) {
+    if key_input.any_just_pressed([KeyCode::Escape, KeyCode::F10]) {
+        options_menu.open = !options_menu.open;
+        options_menu.selected = 0;
+        game_paused.0 = options_menu.open;
+    }
+}
+
+pub fn navigate_options_menu(
+    key_input: Res<Input<KeyCode>>,
+    mut options_menu: ResMut<OptionsMenu>,
+    mut settings: ResMut<DisplaySettings>,
+    mut exit_events: EventWriter<AppExit>,
+) {
+    if !options_menu.open {
+        return;
+    }
+
+    let count = OptionsEntry::ALL.len();
+    if key_input.any_just_pressed([KeyCode::Up, KeyCode::Numpad8]) {
+        options_menu.selected = (options_menu.selected + count - 1) % count;
+    }
+    if key_input.any_just_pressed([KeyCode::Down, KeyCode::Numpad2]) {
+        options_menu.selected = (options_menu.selected + 1) % count;
+    }
+
+    let step = if key_input.any_just_pressed([KeyCode::Left, KeyCode::Numpad4]) {
+        -1
+    } else if key_input.any_just_pressed([
+        KeyCode::Right,
+        KeyCode::Numpad6,
+        KeyCode::Return,
+        KeyCode::NumpadEnter,
+    ]) {
+        1
+    } else {
+        return;
+    };
+
+    match OptionsEntry::ALL[options_menu.selected] {
+        OptionsEntry::Resolution => settings.cycle_resolution(step),
+        OptionsEntry::Mode => settings.cycle_mode(step),
+        OptionsEntry::Vsync => settings.vsync = !settings.vsync,
+        OptionsEntry::UiScale => settings.step_ui_scale(step),
+        OptionsEntry::Quit => {
+            if step > 0 {
+                exit_events.send(AppExit);
+            }
+            return;
+        }
+    }
+    settings.save(SETTINGS_PATH);
+}
+
+pub fn update_options_menu(
+    mut commands: Commands,
+    options_menu: Res<OptionsMenu>,
+    settings: Res<DisplaySettings>,
+    root_query: Query<Entity, With<OptionsMenuRoot>>,
+    mut item_query: Query<(&OptionsMenuItem, &mut Text)>,
+) {
+    if !options_menu.is_changed() && !settings.is_changed() {
+        return;
+    }
+
+    if !options_menu.open {
+        for root in &root_query {
+            commands.entity(root).despawn_recursive();
+        }
+        return;
+    }
+
+    if root_query.is_empty() {
+        spawn_options_menu(&mut commands, &options_menu, &settings);
+        return;
+    }
+
+    for (item, mut text) in &mut item_query {
+        text.sections[0].value = item.0.label(&settings);
+        text.sections[0].style.color = item_color(item.0, &options_menu);
+    }
+}
+
+fn spawn_options_menu(
+    commands: &mut Commands,
+    options_menu: &OptionsMenu,
+    settings: &DisplaySettings,
+) {
+    commands
+        .spawn((
+            Name::new("options menu"),
+            OptionsMenuRoot,
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(8.0),
+                    ..default()
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.8).into(),
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Options",
+                TextStyle {
+                    font_size: 32.0,
+                    color: ITEM_COLOR,
+                    ..default()
+                },
+            ));
+            for entry in OptionsEntry::ALL {
+                parent.spawn((
+                    OptionsMenuItem(entry),
+                    TextBundle::from_section(
+                        entry.label(settings),
+                        TextStyle {
+                            font_size: 20.0,
+                            color: item_color(entry, options_menu),
+                            ..default()
+                        },
+                    ),
+                ));
+            }
+        });
+}
+
+fn item_color(entry: OptionsEntry, options_menu: &OptionsMenu) -> Color {
+    if OptionsEntry::ALL[options_menu.selected] == entry {
+        SELECTED_COLOR
+    } else {
+        ITEM_COLOR
+    }
+}
@@ -1,14 +1,21 @@
 use bevy::prelude::*;
 
 use crate::constants::*;
+use crate::setup::menu_systems::*;
+use crate::setup::resources::{DisplaySettings, OptionsMenu};
 use crate::setup::systems::*;
 use crate::states::MainState;
 
+mod components;
+mod menu_systems;
+pub mod resources;
 mod systems;
 pub struct SetupPlugin;
 
 impl Plugin for SetupPlugin {
     fn build(&self, app: &mut App) {
+        let settings = DisplaySettings::load(SETTINGS_PATH);
+
         app.insert_resource(Msaa::Off)
             .add_state::<MainState>()
             .add_plugins(
@@ -17,15 +24,34 @@ impl Plugin for SetupPlugin {
                     .set(WindowPlugin {
                         primary_window: Some(Window {
                             title: TITLE.into(),
-                            resolution: (WINDOW_WIDTH, WINDOW_HEIGHT).into(),
-                            resizable: false,
+                            resolution: (
+                                settings.resolution.0 as f32,
+                                settings.resolution.1 as f32,
+                            )
+                                .into(),
+                            mode: settings.mode.window_mode(),
+                            present_mode: settings.present_mode(),
+                            resizable: true,
                             ..default()
                         }),
                         ..default()
                     })
                     .build(),
             )
-            .add_systems(Update, bevy::window::close_on_esc)
-            .add_systems(Startup, spawn_camera);
+            .insert_resource(settings)
+            .init_resource::<OptionsMenu>()
+            .add_systems(Startup, spawn_camera)
+            .add_systems(
+                Update,
+                (
+                    record_window_resize,
+                    apply_display_settings.after(record_window_resize),
+                    toggle_options_menu,
+                    navigate_options_menu.after(toggle_options_menu),
+                    update_options_menu
+                        .after(navigate_options_menu)
+                        .after(apply_display_settings),
+                ),
+            );
     }
 }
@@ -0,0 +1,196 @@
+use bevy::prelude::*;
+use bevy::window::{PresentMode, WindowMode};
+
+use crate::constants::*;
+use crate::settings_file;
+
+pub const RESOLUTIONS: [(u32, u32); 5] = [
+    (800, 600),
+    (1024, 768),
+    (1280, 720),
+    (1600, 900),
+    (1920, 1080),
+];
+pub const MIN_UI_SCALE: f64 = 0.5;
+pub const MAX_UI_SCALE: f64 = 2.0;
+pub const UI_SCALE_STEP: f64 = 0.25;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisplayMode {
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    pub const ALL: [DisplayMode; 3] = [
+        DisplayMode::Windowed,
+        DisplayMode::Borderless,
+        DisplayMode::Fullscreen,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DisplayMode::Windowed => "windowed",
+            DisplayMode::Borderless => "borderless",
+            DisplayMode::Fullscreen => "fullscreen",
+        }
+    }
+
+    pub fn window_mode(&self) -> WindowMode {
+        match self {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Borderless => WindowMode::BorderlessFullscreen,
+            DisplayMode::Fullscreen => WindowMode::Fullscreen,
+        }
+    }
+}
+
+/// Display options persisted as `key = value` lines in [`SETTINGS_PATH`].
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct DisplaySettings {
+    pub resolution: (u32, u32),
+    pub mode: DisplayMode,
+    pub vsync: bool,
+    pub ui_scale: f64,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            resolution: (WINDOW_WIDTH as u32, WINDOW_HEIGHT as u32),
+            mode: DisplayMode::Windowed,
+            vsync: true,
+            ui_scale: 1.0,
+        }
+    }
+}
+
+impl DisplaySettings {
+    const KEYS: [&'static str; 4] = ["resolution", "mode", "vsync", "ui_scale"];
+
+    pub fn load(path: &str) -> Self {
+        let mut settings = Self::default();
+        for (key, value) in settings_file::read_entries(path) {
+            // the same file holds settings for other parts of the game
+            if !Self::KEYS.contains(&key.as_str()) {
+                continue;
+            }
+            if !settings.set(&key, &value) {
+                warn!("ignoring invalid setting {} = {}", key, value);
+            }
+        }
+
+        settings
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> bool {
+        let applied = match key {
+            "resolution" => {
+                let parsed = value
+                    .split_once('x')
+                    .and_then(|(width, height)| Some((width.parse().ok()?, height.parse().ok()?)))
+                    .filter(|&(width, height)| width > 0 && height > 0);
+                parsed.map(|resolution| self.resolution = resolution)
+            }
+            "mode" => DisplayMode::ALL
+                .into_iter()
+                .find(|mode| mode.label() == value)
+                .map(|mode| self.mode = mode),
+            "vsync" => value.parse().ok().map(|vsync| self.vsync = vsync),
+            "ui_scale" => value
+                .parse::<f64>()
+                .ok()
+                .filter(|scale| (MIN_UI_SCALE..=MAX_UI_SCALE).contains(scale))
+                .map(|scale| self.ui_scale = scale),
+            _ => None,
+        };
+        applied.is_some()
+    }
+
+    pub fn save(&self, path: &str) {
+        settings_file::write_entries(
+            path,
+            &[
+                (
+                    "resolution",
+                    format!("{}x{}", self.resolution.0, self.resolution.1),
+                ),
+                ("mode", self.mode.label().to_string()),
+                ("vsync", self.vsync.to_string()),
+                ("ui_scale", self.ui_scale.to_string()),
+            ],
+        );
+    }
+
+    pub fn present_mode(&self) -> PresentMode {
+        if self.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        }
+    }
+
+    pub fn cycle_resolution(&mut self, step: isize) {
+        let current = RESOLUTIONS
+            .iter()
+            .position(|resolution| *resolution == self.resolution);
+        let index = match current {
+            Some(index) => (index as isize + step).rem_euclid(RESOLUTIONS.len() as isize),
+            None => 0,
+        };
+        self.resolution = RESOLUTIONS[index as usize];
+    }
+
+    pub fn cycle_mode(&mut self, step: isize) {
+        let index = DisplayMode::ALL
+            .iter()
+            .position(|mode| *mode == self.mode)
+            .unwrap_or_default() as isize;
+        let count = DisplayMode::ALL.len() as isize;
+        self.mode = DisplayMode::ALL[(index + step).rem_euclid(count) as usize];
+    }
+
+    pub fn step_ui_scale(&mut self, step: isize) {
+        self.ui_scale =
+            (self.ui_scale + step as f64 * UI_SCALE_STEP).clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OptionsEntry {
+    Resolution,
+    Mode,
+    Vsync,
+    UiScale,
+    Quit,
+}
+
+impl OptionsEntry {
+    pub const ALL: [OptionsEntry; 5] = [
+        OptionsEntry::Resolution,
+        OptionsEntry::Mode,
+        OptionsEntry::Vsync,
+        OptionsEntry::UiScale,
+        OptionsEntry::Quit,
+    ];
+
+    pub fn label(&self, settings: &DisplaySettings) -> String {
+        match self {
+            OptionsEntry::Resolution => format!(
+                "Resolution: {}x{}",
+                settings.resolution.0, settings.resolution.1
+            ),
+            OptionsEntry::Mode => format!("Mode: {}", settings.mode.label()),
+            OptionsEntry::Vsync => format!("Vsync: {}", if settings.vsync { "on" } else { "off" }),
+            OptionsEntry::UiScale => format!("UI scale: {:.2}", settings.ui_scale),
+            OptionsEntry::Quit => "Quit".to_string(),
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct OptionsMenu {
+    pub open: bool,
+    pub selected: usize,
+}
@@ -1,5 +1,58 @@
 use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized};
+
+use crate::constants::SETTINGS_PATH;
+use crate::setup::resources::{DisplayMode, DisplaySettings};
+
+const RESIZE_SAVE_DELAY: f32 = 0.5;
 
 pub fn spawn_camera(mut commands: Commands) {
     commands.spawn(Camera2dBundle::default());
 }
+
+pub fn apply_display_settings(
+    settings: Res<DisplaySettings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    if let Ok(mut window) = window_query.get_single_mut() {
+        let (width, height) = settings.resolution;
+        if settings.mode == DisplayMode::Windowed
+            && (window.width() as u32, window.height() as u32) != (width, height)
+        {
+            window.resolution.set(width as f32, height as f32);
+        }
+        window.mode = settings.mode.window_mode();
+        window.present_mode = settings.present_mode();
+    }
+    ui_scale.0 = settings.ui_scale;
+}
+
+// keeps the saved resolution in line with the window when it is resized by hand,
+// the file is only written once the resize has settled
+pub fn record_window_resize(
+    mut resize_events: EventReader<WindowResized>,
+    mut settings: ResMut<DisplaySettings>,
+    mut save_timer: Local<Option<Timer>>,
+    time: Res<Time>,
+) {
+    if let Some(event) = resize_events.read().last() {
+        let resolution = (event.width.round() as u32, event.height.round() as u32);
+        if settings.mode == DisplayMode::Windowed && settings.resolution != resolution {
+            settings.resolution = resolution;
+            *save_timer = Some(Timer::from_seconds(RESIZE_SAVE_DELAY, TimerMode::Once));
+        }
+    }
+
+    if save_timer
+        .as_mut()
+        .is_some_and(|timer| timer.tick(time.delta()).finished())
+    {
+        settings.save(SETTINGS_PATH);
+        *save_timer = None;
+    }
+}
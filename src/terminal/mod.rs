@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::input::InputSystem;
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+
+use crate::states::MainState;
+use crate::terminal::resources::{TerminalInput, TerminalSettings};
+use crate::terminal::systems::*;
+
+pub mod resources;
+mod systems;
+
+/// Draws the game as coloured glyphs on stdout and reads commands from stdin,
+/// in place of `SetupPlugin`, `AssetsPlugin` and `GraphicsPlugin`.
+pub struct TerminalPlugin;
+
+impl Plugin for TerminalPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(
+                1.0 / 30.0,
+            ))),
+            LogPlugin::default(),
+            bevy::input::InputPlugin,
+        ))
+        .add_state::<MainState>()
+        .init_resource::<TerminalInput>()
+        .init_resource::<TerminalSettings>()
+        .add_systems(Startup, start_game)
+        .add_systems(PreUpdate, read_terminal_input.after(InputSystem))
+        .add_systems(
+            PostUpdate,
+            (update_animation_lock, draw_terminal).run_if(in_state(MainState::Game)),
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use std::io::BufRead;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Mutex;
+
+use bevy::prelude::*;
+
+/// Commands typed on stdin, one line per command.
+#[derive(Resource)]
+pub struct TerminalInput(pub Mutex<Receiver<String>>);
+
+impl Default for TerminalInput {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        // stdin blocks, so it is read on its own thread; the channel closes on end of input
+        std::thread::spawn(move || {
+            for line in std::io::stdin().lock().lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Self(Mutex::new(receiver))
+    }
+}
+
+#[derive(Resource)]
+pub struct TerminalSettings {
+    pub width: i32,
+    pub height: i32,
+    // honours the `NO_COLOR` convention, for logs and dumb terminals
+    pub color: bool,
+}
+
+impl Default for TerminalSettings {
+    fn default() -> Self {
+        Self {
+            width: 80,
+            height: 21,
+            color: std::env::var_os("NO_COLOR").is_none(),
+        }
+    }
+}
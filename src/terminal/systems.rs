@@ -0,0 +1,398 @@
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::sync::mpsc::TryRecvError;
+
+use bevy::app::AppExit;
+use bevy::ecs::world::EntityRef;
+use bevy::prelude::*;
+
+use crate::game::components::*;
+use crate::game::resources::*;
+use crate::game::services::lighting;
+use crate::game::templates::{ItemClass, MonsterKind};
+use crate::game::vector::GridVector;
+use crate::states::MainState;
+use crate::terminal::resources::{TerminalInput, TerminalSettings};
+
+// long enough to see each turn being drawn when playing by hand
+const TURN_BEAT: f32 = 0.05;
+const REMEMBERED_COLOR: Color = Color::rgb(0.3, 0.3, 0.35);
+
+pub fn start_game(mut next_state: ResMut<NextState<MainState>>) {
+    next_state.set(MainState::Game);
+}
+
+// each line is pressed as one chord of keys for a single frame
+pub fn read_terminal_input(
+    terminal_input: Res<TerminalInput>,
+    accept_input: Res<AcceptInput>,
+    mut key_input: ResMut<Input<KeyCode>>,
+    mut exit_events: EventWriter<AppExit>,
+) {
+    key_input.release_all();
+    if !accept_input.0 {
+        return;
+    }
+
+    let line = match terminal_input.0.lock().unwrap().try_recv() {
+        Ok(line) => line,
+        Err(TryRecvError::Empty) => return,
+        Err(TryRecvError::Disconnected) => {
+            exit_events.send(AppExit);
+            return;
+        }
+    };
+
+    let command = line.trim();
+    if command == "q" {
+        exit_events.send(AppExit);
+        return;
+    }
+    for key in keys_for_command(command) {
+        key_input.press(key);
+    }
+}
+
+fn keys_for_command(command: &str) -> Vec<KeyCode> {
+    match command {
+        "enter" => return vec![KeyCode::Return],
+        "tab" => return vec![KeyCode::Tab],
+        "back" => return vec![KeyCode::Back],
+        _ => {}
+    }
+
+    let mut keys = Vec::new();
+    let mut slot_prefix = false;
+    for character in command.chars() {
+        match character {
+            // after throw, use or equip a digit is only an inventory slot, numpad 5 would wait
+            '1'..='9' if slot_prefix => keys.push(DIGIT_KEYS[character as usize - '1' as usize]),
+            // otherwise digits are both numpad directions and perk slots, the game picks by context
+            '1'..='9' => {
+                let index = character as usize - '1' as usize;
+                keys.extend([NUMPAD_KEYS[index], DIGIT_KEYS[index]]);
+            }
+            '^' => keys.push(KeyCode::ControlLeft),
+            '.' => keys.push(KeyCode::Period),
+            'a'..='z' => keys.push(LETTER_KEYS[character as usize - 'a' as usize]),
+            _ => {}
+        }
+        slot_prefix = matches!(character, 't' | 'u' | 'e');
+    }
+    keys
+}
+
+const NUMPAD_KEYS: [KeyCode; 9] = [
+    KeyCode::Numpad1,
+    KeyCode::Numpad2,
+    KeyCode::Numpad3,
+    KeyCode::Numpad4,
+    KeyCode::Numpad5,
+    KeyCode::Numpad6,
+    KeyCode::Numpad7,
+    KeyCode::Numpad8,
+    KeyCode::Numpad9,
+];
+
+const DIGIT_KEYS: [KeyCode; 9] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+
+const LETTER_KEYS: [KeyCode; 26] = [
+    KeyCode::A,
+    KeyCode::B,
+    KeyCode::C,
+    KeyCode::D,
+    KeyCode::E,
+    KeyCode::F,
+    KeyCode::G,
+    KeyCode::H,
+    KeyCode::I,
+    KeyCode::J,
+    KeyCode::K,
+    KeyCode::L,
+    KeyCode::M,
+    KeyCode::N,
+    KeyCode::O,
+    KeyCode::P,
+    KeyCode::Q,
+    KeyCode::R,
+    KeyCode::S,
+    KeyCode::T,
+    KeyCode::U,
+    KeyCode::V,
+    KeyCode::W,
+    KeyCode::X,
+    KeyCode::Y,
+    KeyCode::Z,
+];
+
+// there is nothing to animate, but a short pause keeps turns readable
+pub fn update_animation_lock(
+    mut animation_lock: ResMut<AnimationLock>,
+    accept_input: Res<AcceptInput>,
+    mut turn_timer: Local<Option<Timer>>,
+    time: Res<Time>,
+) {
+    if accept_input.is_changed() && !accept_input.0 {
+        *turn_timer = Some(Timer::from_seconds(TURN_BEAT, TimerMode::Once));
+    }
+
+    animation_lock.0 = turn_timer
+        .as_mut()
+        .is_some_and(|timer| !timer.tick(time.delta()).finished());
+}
+
+pub fn draw_terminal(world: &mut World, mut had_targeting: Local<bool>) {
+    let has_targeting = world.contains_resource::<Targeting>();
+    let changed = world.is_resource_changed::<GameTime>()
+        || world.is_resource_changed::<VisibleTiles>()
+        || world.is_resource_changed::<Targeting>()
+        || world.is_resource_changed::<PerkChoice>()
+        || has_targeting != *had_targeting;
+    *had_targeting = has_targeting;
+    if !changed {
+        return;
+    }
+
+    let mut query = world.query_filtered::<(Entity, &GridPosition), With<PlayerControlled>>();
+    let Ok((player, center)) = query
+        .get_single(world)
+        .map(|(player, position)| (player, position.coordinates))
+    else {
+        return;
+    };
+
+    let settings = world.resource::<TerminalSettings>();
+    let (width, height, color) = (settings.width, settings.height, settings.color);
+    let origin = center + GridVector::new(-width / 2, height / 2);
+
+    // the topmost renderable layer wins each cell
+    let mut cells = vec![None; (width * height) as usize];
+    let mut query = world.query_filtered::<(Entity, &GridPosition, &Renderable), With<MapEntity>>();
+    for (entity, position, renderable) in query.iter(world) {
+        let column = position.coordinates.x - origin.x;
+        let row = origin.y - position.coordinates.y;
+        if !(0..width).contains(&column) || !(0..height).contains(&row) {
+            continue;
+        }
+        let Some(glyph) = get_glyph(world.entity(entity), position.coordinates, center, world)
+        else {
+            continue;
+        };
+
+        let cell = &mut cells[(row * width + column) as usize];
+        if cell.is_none_or(|(layer, _, _)| renderable.layer >= layer) {
+            *cell = Some((renderable.layer, glyph.0, glyph.1));
+        }
+    }
+
+    let cursor = world
+        .get_resource::<Targeting>()
+        .map(|targeting| targeting.cursor);
+
+    let mut frame = String::new();
+    if color {
+        // clear the screen and home the cursor
+        frame.push_str("\x1b[2J\x1b[H");
+    } else {
+        frame.push_str(&"-".repeat(width as usize));
+        frame.push('\n');
+    }
+    for row in 0..height {
+        for column in 0..width {
+            let coordinates = origin + GridVector::new(column, -row);
+            let (character, glyph_color) = if cursor == Some(coordinates) {
+                ('X', Color::YELLOW)
+            } else {
+                cells[(row * width + column) as usize]
+                    .map_or((' ', Color::BLACK), |(_, character, glyph_color)| {
+                        (character, glyph_color)
+                    })
+            };
+            push_glyph(&mut frame, character, glyph_color, color);
+        }
+        if color {
+            frame.push_str("\x1b[0m");
+        }
+        frame.push('\n');
+    }
+
+    frame.push_str(&status_line(player, world));
+    frame.push('\n');
+    frame.push_str(&inventory_line(player, world));
+    frame.push('\n');
+    if let Some(targeting) = world.get_resource::<Targeting>() {
+        frame.push_str(&targeting.description);
+        frame.push('\n');
+    }
+    if let Some(perk_choice) = world.get_resource::<PerkChoice>() {
+        frame.push_str("choose a perk:");
+        for (index, perk) in perk_choice.options.iter().enumerate() {
+            let _ = write!(frame, " {}) {}", index + 1, perk.label());
+        }
+        frame.push('\n');
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    let _ = stdout.write_all(frame.as_bytes());
+    let _ = stdout.flush();
+}
+
+// mirrors the fog of war rules of the sprite renderer
+fn get_glyph(
+    entity: EntityRef,
+    coordinates: GridVector,
+    viewer: GridVector,
+    world: &World,
+) -> Option<(char, Color)> {
+    let light_map = world.resource::<LightMap>();
+    let visible = world.resource::<VisibleTiles>().contains(&coordinates);
+    let explored = world.resource::<ExploredTiles>().contains(&coordinates);
+    let is_character = entity.contains::<Character>();
+    let is_item = entity.contains::<Item>();
+
+    if entity.get::<Trap>().is_some_and(|trap| trap.hidden) {
+        return None;
+    }
+    if !visible && (is_character || is_item || !explored) {
+        return None;
+    }
+    if is_character && !lighting::is_lit_for(viewer, coordinates, light_map) {
+        return None;
+    }
+
+    let (character, glyph_color) = if entity.contains::<PlayerControlled>() {
+        ('@', Color::WHITE)
+    } else if let Some(monster) = entity.get::<Monster>() {
+        monster_glyph(monster.kind)
+    } else if let Some(item) = entity.get::<Item>() {
+        item_glyph(item.kind.template().class)
+    } else if entity.contains::<Trap>() {
+        ('^', Color::ORANGE_RED)
+    } else if let Some(door) = entity.get::<Door>() {
+        match (door.secret, door.closed) {
+            (true, _) => ('#', Color::GRAY),
+            (false, true) => ('+', Color::rgb(0.7, 0.45, 0.2)),
+            (false, false) => ('\'', Color::rgb(0.7, 0.45, 0.2)),
+        }
+    } else if entity.contains::<Stairs>() {
+        ('>', Color::WHITE)
+    } else if entity.contains::<Torch>() {
+        ('*', Color::ORANGE)
+    } else if entity.contains::<Solid>() {
+        ('#', Color::GRAY)
+    } else if entity.contains::<Floor>() {
+        ('.', Color::GRAY)
+    } else {
+        return None;
+    };
+
+    if !visible {
+        return Some((character, REMEMBERED_COLOR));
+    }
+    let light = lighting::get_light_level(coordinates, light_map);
+    Some((character, glyph_color * light.max(0.5)))
+}
+
+fn monster_glyph(kind: MonsterKind) -> (char, Color) {
+    match kind {
+        MonsterKind::Rat => ('r', Color::rgb(0.6, 0.5, 0.4)),
+        MonsterKind::Goblin => ('g', Color::GREEN),
+        MonsterKind::Orc => ('o', Color::rgb(0.4, 0.7, 0.3)),
+        MonsterKind::Skeleton => ('s', Color::rgb(0.8, 0.85, 1.0)),
+        MonsterKind::Troll => ('T', Color::rgb(0.5, 0.6, 0.3)),
+        MonsterKind::Dog => ('d', Color::rgb(0.8, 0.6, 0.3)),
+    }
+}
+
+fn item_glyph(class: ItemClass) -> (char, Color) {
+    match class {
+        ItemClass::Misc => ('*', Color::WHITE),
+        ItemClass::Weapon => (')', Color::SILVER),
+        ItemClass::Armour => ('[', Color::SILVER),
+        ItemClass::Potion => ('!', Color::FUCHSIA),
+        ItemClass::Scroll => ('?', Color::WHITE),
+        ItemClass::Ring => ('=', Color::GOLD),
+        ItemClass::Food => ('%', Color::rgb(0.8, 0.6, 0.3)),
+    }
+}
+
+fn push_glyph(frame: &mut String, character: char, glyph_color: Color, color: bool) {
+    if color {
+        let [red, green, blue, _] = glyph_color.as_rgba_u8();
+        let _ = write!(frame, "\x1b[38;2;{};{};{}m", red, green, blue);
+    }
+    frame.push(character);
+}
+
+fn status_line(player: Entity, world: &World) -> String {
+    let player = world.entity(player);
+    let health = player.get::<Health>().map_or(String::new(), |health| {
+        format!("HP {}/{}", health.current, health.max)
+    });
+    let level = player
+        .get::<Experience>()
+        .map_or(String::new(), |experience| {
+            format!("LV {}", experience.level)
+        });
+    let effects = player
+        .get::<StatusEffects>()
+        .map(|status_effects| {
+            status_effects
+                .iter()
+                .map(|effect| format!("{:?}", effect.kind))
+                .collect::<Vec<String>>()
+                .join(" ")
+        })
+        .unwrap_or_default();
+
+    format!(
+        "{}  {}  Depth {}  Turn {}  {}",
+        health,
+        level,
+        world.resource::<DungeonDepth>().0,
+        world.resource::<GameTime>().0.floor(),
+        effects
+    )
+}
+
+// numbered like the slot keys used to throw, use and equip
+fn inventory_line(player: Entity, world: &World) -> String {
+    let player = world.entity(player);
+    let Some(inventory) = player.get::<Inventory>() else {
+        return String::new();
+    };
+    let equipment = player.get::<Equipment>();
+    let appearances = world.resource::<ItemAppearances>();
+    let identified = world.resource::<IdentifiedItems>();
+
+    let items = inventory
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| {
+            let kind = world.get::<Item>(*item)?.kind;
+            let equipped = equipment.is_some_and(|equipment| equipment.contains(*item));
+            Some(format!(
+                "{}) {}{}",
+                index + 1,
+                appearances.name_of(kind, identified),
+                if equipped { "*" } else { "" }
+            ))
+        })
+        .collect::<Vec<String>>();
+
+    if items.is_empty() {
+        "pack: empty".to_string()
+    } else {
+        format!("pack: {}", items.join("  "))
+    }
+}